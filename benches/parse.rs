@@ -0,0 +1,91 @@
+//! Benchmark parsing of a large synthetic pacman logfile.
+#![allow(missing_docs)]
+use std::hint::black_box;
+use std::str::FromStr;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use pacmanlog::{Entries, Entry, EntryRef, Transactions};
+
+const TRANSACTIONS: usize = 10_000;
+
+fn synthetic_log() -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for index in 0..TRANSACTIONS {
+        let time = format!(
+            "[2024-03-04T12:{:02}:{:02}+0100]",
+            index / 60 % 60,
+            index % 60
+        );
+        lines.push(format!("{time} [PACMAN] Running 'pacman -Syu --noconfirm'"));
+        lines.push(format!("{time} [PACMAN] synchronizing package lists"));
+        lines.push(format!("{time} [PACMAN] starting full system upgrade"));
+        lines.push(format!("{time} [ALPM] transaction started"));
+        lines.push(format!(
+            "{time} [ALPM] installed foo-{index} (1.0.{index}-1)"
+        ));
+        lines.push(format!(
+            "{time} [ALPM] upgraded bar-{index} (1.0.{index}-1 -> 1.0.{index}-2)"
+        ));
+        lines.push(format!("{time} [ALPM] removed baz-{index} (0.{index}-1)"));
+        lines.push(format!("{time} [ALPM-SCRIPTLET] ==> Building image"));
+        lines.push(format!("{time} [ALPM] transaction completed"));
+        lines.push(format!(
+            "{time} [ALPM] running '90-mkinitcpio-install.hook'..."
+        ));
+    }
+
+    lines
+}
+
+fn parse(criterion: &mut Criterion) {
+    let lines = synthetic_log();
+    let mut group = criterion.benchmark_group("parse");
+    group.throughput(Throughput::Elements(lines.len() as u64));
+    group.bench_function("entry_from_str", |bencher| {
+        bencher.iter(|| {
+            for line in &lines {
+                black_box(Entry::from_str(black_box(line)).ok());
+            }
+        });
+    });
+    group.bench_function("entry_ref_try_from", |bencher| {
+        bencher.iter(|| {
+            for line in &lines {
+                black_box(EntryRef::try_from(black_box(line.as_str())).ok());
+            }
+        });
+    });
+    group.bench_function("entries", |bencher| {
+        bencher.iter(|| black_box(lines.iter().cloned().entries().count()));
+    });
+    group.bench_function("transactions", |bencher| {
+        bencher.iter(|| {
+            black_box(
+                lines
+                    .iter()
+                    .cloned()
+                    .entries()
+                    .filter_map(Result::ok)
+                    .transactions()
+                    .count(),
+            )
+        });
+    });
+    group.bench_function("transactions_borrowed", |bencher| {
+        bencher.iter(|| {
+            black_box(
+                lines
+                    .iter()
+                    .filter_map(|line| EntryRef::try_from(line.as_str()).ok())
+                    .transactions()
+                    .count(),
+            )
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
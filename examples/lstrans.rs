@@ -1,11 +1,7 @@
 //! List all transactions from the pacman logfile.
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader};
-use std::str::FromStr;
-
 use clap::Parser;
 
-use pacmanlog::{Entry, Transactions};
+use pacmanlog::{LogReader, Transactions};
 
 const DEFAULT_FILE: &str = "/var/log/pacman.log";
 
@@ -19,15 +15,10 @@ fn main() {
     env_logger::init();
     let args = Args::parse();
 
-    for transaction in BufReader::new(
-        OpenOptions::new()
-            .read(true)
-            .open(args.file)
-            .expect("Failed to open file"),
-    )
-    .lines()
-    .filter_map(|line| line.ok().and_then(|line| Entry::from_str(&line).ok()))
-    .transactions()
+    for transaction in LogReader::open(args.file)
+        .expect("Failed to open file")
+        .filter_map(Result::ok)
+        .transactions()
     {
         println!("{transaction:?}");
     }
@@ -1,11 +1,7 @@
 //! Find the largest transaction by amount of affected packages in the pacman logfile.
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader};
-use std::str::FromStr;
-
 use clap::Parser;
 
-use pacmanlog::{Entry, Transactions};
+use pacmanlog::{LogReader, Transactions};
 
 const DEFAULT_FILE: &str = "/var/log/pacman.log";
 
@@ -18,16 +14,11 @@ struct Args {
 fn main() {
     env_logger::init();
     let args = Args::parse();
-    let transaction = BufReader::new(
-        OpenOptions::new()
-            .read(true)
-            .open(args.file)
-            .expect("Failed to open file"),
-    )
-    .lines()
-    .filter_map(|line| line.ok().and_then(|line| Entry::from_str(&line).ok()))
-    .transactions()
-    .max_by(|a, b| a.len().cmp(&b.len()))
-    .expect("No transactions found");
+    let transaction = LogReader::open(args.file)
+        .expect("Failed to open file")
+        .filter_map(Result::ok)
+        .transactions()
+        .max_by(|a, b| a.len().cmp(&b.len()))
+        .expect("No transactions found");
     println!("{transaction:?}");
 }
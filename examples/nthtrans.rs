@@ -1,11 +1,7 @@
 //! Print the nth transaction from the pacman logfile.
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader};
-use std::str::FromStr;
-
 use clap::Parser;
 
-use pacmanlog::{Entry, Transaction, Transactions};
+use pacmanlog::{LogReader, ReverseReader, Transaction, Transactions};
 
 const DEFAULT_FILE: &str = "/var/log/pacman.log";
 
@@ -17,16 +13,25 @@ struct Args {
     file: String,
     #[clap(long, short)]
     packages: bool,
+    #[clap(long, short)]
+    command: bool,
 }
 
 impl Args {
-    pub fn nth_transaction<'a>(&self, transactions: &'a [Transaction]) -> Option<&'a Transaction> {
+    pub fn nth_transaction(&self) -> Option<Transaction> {
         if self.index < 0 {
-            transactions.len().checked_add_signed(self.index)
+            ReverseReader::open(&self.file)
+                .expect("Failed to open file")
+                .filter_map(Result::ok)
+                .reverse_transactions()
+                .nth(self.index.unsigned_abs() - 1)
         } else {
-            Some(self.index.abs_diff(0))
+            LogReader::open(&self.file)
+                .expect("Failed to open file")
+                .filter_map(Result::ok)
+                .transactions()
+                .nth(self.index.unsigned_abs())
         }
-        .and_then(|index| transactions.get(index))
     }
 }
 
@@ -34,20 +39,11 @@ fn main() {
     env_logger::init();
     let args = Args::parse();
 
-    let transactions: Vec<Transaction> = BufReader::new(
-        OpenOptions::new()
-            .read(true)
-            .open(&args.file)
-            .expect("Failed to open file"),
-    )
-    .lines()
-    .filter_map(|line| line.ok().and_then(|line| Entry::from_str(&line).ok()))
-    .transactions()
-    .collect();
-
-    if let Some(transaction) = args.nth_transaction(&transactions) {
+    if let Some(transaction) = args.nth_transaction() {
         if args.packages {
-            print_packages(transaction);
+            print_packages(&transaction);
+        } else if args.command {
+            print_command(&transaction);
         } else {
             println!("{transaction:?}");
         }
@@ -64,3 +60,9 @@ fn print_packages(transaction: &Transaction) {
             .join(" ")
     );
 }
+
+fn print_command(transaction: &Transaction) {
+    if let Some(command) = transaction.command() {
+        println!("{}", command.argv().join(" "));
+    }
+}
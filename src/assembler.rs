@@ -0,0 +1,129 @@
+use chrono::{FixedOffset, Offset, Utc};
+
+use crate::{Entry, Error};
+
+/// Assembles entries from lines, appending continuation lines to the previous entry.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Assembler {
+    offset: FixedOffset,
+    pending: Option<Entry>,
+}
+
+/// Result of pushing a line into an [`Assembler`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Push {
+    /// The line started a new entry, completing the previous one if any.
+    Started(Option<Entry>),
+    /// The line was appended to the previous entry.
+    Continued,
+    /// The line could not be parsed.
+    Failed(Error),
+}
+
+impl Assembler {
+    /// Create a new assembler that assumes the given offset for timestamps without a time zone.
+    pub const fn new(offset: FixedOffset) -> Self {
+        Self {
+            offset,
+            pending: None,
+        }
+    }
+
+    /// Return the offset assumed for timestamps without a time zone.
+    pub const fn offset(&self) -> FixedOffset {
+        self.offset
+    }
+
+    /// Return whether an entry is pending.
+    pub const fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Push a line into the assembler.
+    pub fn push(&mut self, line: &str) -> Push {
+        match Entry::from_str_with_offset(line, self.offset) {
+            Ok(entry) => Push::Started(self.pending.replace(entry)),
+            Err(error @ (Error::MalformedEntry(_) | Error::InvalidTimestamp(_))) => {
+                if self
+                    .pending
+                    .as_mut()
+                    .is_some_and(|entry| entry.append_line(line))
+                {
+                    Push::Continued
+                } else {
+                    Push::Failed(error)
+                }
+            }
+            Err(error) => Push::Failed(error),
+        }
+    }
+
+    /// Return the last pending entry.
+    pub const fn finish(&mut self) -> Option<Entry> {
+        self.pending.take()
+    }
+}
+
+impl Default for Assembler {
+    fn default() -> Self {
+        Self::new(Utc.fix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Assembler, Push};
+    use crate::{Error, Message};
+
+    #[test]
+    fn appends_continuation_lines() {
+        let mut assembler = Assembler::default();
+        assert_eq!(
+            assembler
+                .push("[2024-03-04T12:34:56+0100] [ALPM-SCRIPTLET] ==> Building image from preset"),
+            Push::Started(None)
+        );
+        assert_eq!(
+            assembler.push("  -> -k /boot/vmlinuz-linux -g /boot/initramfs-linux.img"),
+            Push::Continued
+        );
+        assert_eq!(
+            assembler.push("[ OK ] Generated initramfs"),
+            Push::Continued
+        );
+
+        let Push::Started(Some(entry)) =
+            assembler.push("[2024-03-04T12:34:57+0100] [ALPM] transaction completed")
+        else {
+            panic!("entry has not been completed");
+        };
+        assert_eq!(
+            entry.message(),
+            &Message::Other(
+                "==> Building image from preset\n  -> -k /boot/vmlinuz-linux -g /boot/initramfs-linux.img\n[ OK ] Generated initramfs"
+                    .to_string()
+            )
+        );
+        assert!(assembler.finish().is_some());
+        assert!(assembler.finish().is_none());
+    }
+
+    #[test]
+    fn rejects_continuation_without_entry() {
+        let mut assembler = Assembler::default();
+        assert!(matches!(
+            assembler.push("  -> Running build hook: [base]"),
+            Push::Failed(Error::MalformedEntry(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_continuation_of_structured_message() {
+        let mut assembler = Assembler::default();
+        assembler.push("[2024-03-04T12:34:56+0100] [ALPM] installed vim (9.1.0142-1)");
+        assert!(matches!(
+            assembler.push("stray output"),
+            Push::Failed(Error::MalformedEntry(_))
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use log::warn;
+use tokio::io::AsyncBufRead;
+
+use crate::{AsyncLogReader, Entry, ReadSummary};
+
+/// Yields the entries of an [`AsyncLogReader`], skipping lines that could not be read.
+///
+/// Skipped lines are logged and counted in the [`ReadSummary`].
+#[derive(Debug)]
+pub struct AsyncEntries<R> {
+    reader: AsyncLogReader<R>,
+}
+
+impl<R> AsyncEntries<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Return a summary of the lines read so far.
+    #[must_use]
+    pub const fn summary(&self) -> &ReadSummary {
+        self.reader.summary()
+    }
+}
+
+impl<R> From<AsyncLogReader<R>> for AsyncEntries<R> {
+    fn from(reader: AsyncLogReader<R>) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R> Stream for AsyncEntries<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    type Item = Entry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match ready!(Pin::new(&mut this.reader).poll_next(cx)) {
+                Some(Ok(entry)) => return Poll::Ready(Some(entry)),
+                Some(Err(error)) => warn!("skipping unreadable line: {error}"),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use chrono::FixedOffset;
+use futures_core::Stream;
+use tokio::fs::File;
+use tokio::io::{AsyncBufRead, BufReader};
+
+use crate::reader::Parser;
+use crate::{AsyncEntries, Entry, ReadError, ReadSummary};
+
+/// Asynchronously reads entries from a pacman logfile.
+///
+/// This is the [`Stream`] counterpart of [`LogReader`](crate::LogReader) and parses lines the same way.
+#[derive(Debug)]
+pub struct AsyncLogReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    parser: Parser,
+    done: bool,
+}
+
+impl AsyncLogReader<BufReader<File>> {
+    /// Open the logfile at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be opened.
+    pub async fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        File::open(path)
+            .await
+            .map(|file| Self::new(BufReader::new(file)))
+    }
+}
+
+impl<R> AsyncLogReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Create a new reader from any asynchronous buffered reader.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            parser: Parser::default(),
+            done: false,
+        }
+    }
+
+    /// Assume the given offset for timestamps without a time zone.
+    #[must_use]
+    pub fn with_default_offset(mut self, offset: FixedOffset) -> Self {
+        self.parser.set_default_offset(offset);
+        self
+    }
+
+    /// Return a summary of the lines read so far.
+    #[must_use]
+    pub const fn summary(&self) -> &ReadSummary {
+        self.parser.summary()
+    }
+
+    /// Return the byte offset at which the most recently yielded entry starts.
+    #[must_use]
+    pub const fn entry_offset(&self) -> u64 {
+        self.parser.entry_offset()
+    }
+
+    /// Return a stream of the entries that skips lines that could not be read.
+    ///
+    /// The returned stream can be grouped into transactions with [`AsyncTransactions`](crate::AsyncTransactions).
+    #[must_use]
+    pub fn entries(self) -> AsyncEntries<R> {
+        AsyncEntries::from(self)
+    }
+}
+
+impl<R> Stream for AsyncLogReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    type Item = Result<Entry, ReadError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(error) = this.parser.queued() {
+            return Poll::Ready(Some(Err(error)));
+        }
+
+        while !this.done {
+            let available = match ready!(Pin::new(&mut this.reader).poll_fill_buf(cx)) {
+                Ok(available) => available,
+                Err(error) => {
+                    this.done = true;
+                    return Poll::Ready(Some(this.parser.io_error(error, &this.buffer)));
+                }
+            };
+
+            if available.is_empty() {
+                this.done = true;
+
+                if this.buffer.is_empty() {
+                    break;
+                }
+            } else if let Some(index) = available.iter().position(|&byte| byte == b'\n') {
+                this.buffer.extend_from_slice(&available[..=index]);
+                Pin::new(&mut this.reader).consume(index + 1);
+            } else {
+                let size = available.len();
+                this.buffer.extend_from_slice(available);
+                Pin::new(&mut this.reader).consume(size);
+                continue;
+            }
+
+            let result = this.parser.line(&this.buffer);
+            this.buffer.clear();
+
+            if let Some(result) = result {
+                return Poll::Ready(Some(result));
+            }
+        }
+
+        Poll::Ready(this.parser.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    use futures_core::Stream;
+    use tokio::io::BufReader;
+
+    use super::AsyncLogReader;
+    use crate::fixture::LOG;
+    use crate::{AsyncTransactions, LogReader, ReadError, Transactions};
+
+    /// Collect a stream whose reader never has to wait.
+    fn collect<S>(mut stream: S) -> Vec<S::Item>
+    where
+        S: Stream + Unpin,
+    {
+        let mut context = Context::from_waker(Waker::noop());
+        let mut items = Vec::new();
+
+        loop {
+            match Pin::new(&mut stream).poll_next(&mut context) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => return items,
+                Poll::Pending => panic!("stream is pending"),
+            }
+        }
+    }
+
+    fn reader() -> AsyncLogReader<BufReader<&'static [u8]>> {
+        AsyncLogReader::new(BufReader::with_capacity(7, LOG.as_bytes()))
+    }
+
+    #[test]
+    fn yields_the_same_entries_as_log_reader() {
+        let log = format!("{LOG}[2024-03-05T00:00:00+0100] [ALPM] installed\n{LOG}");
+        let positions = |results: Vec<Result<_, ReadError>>| {
+            results
+                .into_iter()
+                .map(|result| result.map_err(|error| (error.line(), error.offset())))
+                .collect::<Vec<_>>()
+        };
+        let reader = AsyncLogReader::new(BufReader::with_capacity(7, log.as_bytes()));
+        assert_eq!(
+            positions(collect(reader)),
+            positions(LogReader::new(log.as_bytes()).collect())
+        );
+    }
+
+    #[test]
+    fn groups_the_same_transactions_as_iterators() {
+        let entries = || LogReader::new(LOG.as_bytes()).map(Result::unwrap);
+        assert_eq!(
+            collect(reader().entries().transactions()),
+            entries().transactions().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            collect(reader().entries().streaming_transactions()),
+            entries().streaming_transactions().collect::<Vec<_>>()
+        );
+    }
+}
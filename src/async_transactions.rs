@@ -0,0 +1,34 @@
+use futures_core::Stream;
+
+use crate::{IntoEntry, TransactionsStream};
+
+/// Group streamed entries into transactions.
+///
+/// This is the [`Stream`] counterpart of [`Transactions`](crate::Transactions).
+pub trait AsyncTransactions: Stream + Sized
+where
+    Self::Item: IntoEntry,
+{
+    /// Return a stream of [`Transaction`](crate::Transaction)s.
+    fn transactions(self) -> TransactionsStream<Self>;
+
+    /// Return a stream of [`Transaction`](crate::Transaction)s that yields each transaction
+    /// as soon as its `transaction completed` or `transaction interrupted` entry has been read.
+    ///
+    /// Hooks logged after the completion entry are not part of the yielded transactions.
+    fn streaming_transactions(self) -> TransactionsStream<Self>;
+}
+
+impl<T> AsyncTransactions for T
+where
+    T: Stream,
+    T::Item: IntoEntry,
+{
+    fn transactions(self) -> TransactionsStream<Self> {
+        TransactionsStream::from(self)
+    }
+
+    fn streaming_transactions(self) -> TransactionsStream<Self> {
+        TransactionsStream::eager(self)
+    }
+}
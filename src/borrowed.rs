@@ -0,0 +1,15 @@
+pub use config_file::ConfigFileRef;
+pub use downgrade::DowngradeRef;
+pub use entry::EntryRef;
+pub use issuer::IssuerRef;
+pub use message::MessageRef;
+pub use package::PackageRef;
+pub use upgrade::UpgradeRef;
+
+mod config_file;
+mod downgrade;
+mod entry;
+mod issuer;
+mod message;
+mod package;
+mod upgrade;
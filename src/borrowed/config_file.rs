@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use crate::ConfigFile;
+
+/// Borrowed information about a configuration file that has been installed or saved under a new path.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ConfigFileRef<'a> {
+    original: &'a Path,
+    new_path: &'a Path,
+}
+
+impl<'a> ConfigFileRef<'a> {
+    /// Parse `<original> installed as <new path>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the text if it could not be parsed.
+    pub fn pacnew(text: &'a str) -> Result<Self, String> {
+        Self::parse(text, " installed as ")
+    }
+
+    /// Parse `<original> saved as <new path>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the text if it could not be parsed.
+    pub fn pacsave(text: &'a str) -> Result<Self, String> {
+        Self::parse(text, " saved as ")
+    }
+
+    /// Return the path of the original file.
+    #[must_use]
+    pub const fn original(&self) -> &'a Path {
+        self.original
+    }
+
+    /// Return the path that the file has been installed or saved as.
+    #[must_use]
+    pub const fn new_path(&self) -> &'a Path {
+        self.new_path
+    }
+
+    /// Convert into an owned [`ConfigFile`].
+    #[must_use]
+    pub fn to_owned(&self) -> ConfigFile {
+        ConfigFile::new(self.original.to_path_buf(), self.new_path.to_path_buf())
+    }
+
+    fn parse(text: &'a str, separator: &str) -> Result<Self, String> {
+        let (original, new_path) = text
+            .split_once(separator)
+            .filter(|(original, new_path)| !original.is_empty() && !new_path.is_empty())
+            .ok_or_else(|| text.to_string())?;
+        Ok(Self {
+            original: Path::new(original),
+            new_path: Path::new(new_path),
+        })
+    }
+}
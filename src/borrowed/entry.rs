@@ -0,0 +1,201 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, Utc};
+
+use crate::error::Error;
+use crate::{tokenizer, Entry, EntryFormat};
+
+use super::{IssuerRef, MessageRef};
+
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%z";
+const LEGACY_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// A log file entry borrowing from the line it was parsed from.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EntryRef<'a> {
+    timestamp: DateTime<FixedOffset>,
+    issuer: IssuerRef<'a>,
+    message: MessageRef<'a>,
+    format: EntryFormat,
+}
+
+impl<'a> EntryRef<'a> {
+    /// Parse an entry, assuming the given offset for timestamps without a time zone.
+    ///
+    /// [`EntryRef::try_from`] assumes UTC for such timestamps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the entry could not be parsed.
+    pub fn parse_with_offset(s: &'a str, offset: FixedOffset) -> Result<Self, Error> {
+        let (timestamp, issuer, message) =
+            tokenizer::entry(s).ok_or_else(|| Error::MalformedEntry(s.to_string()))?;
+        let (timestamp, format) = parse_timestamp(timestamp, offset)?;
+        let (issuer, format) = issuer.map_or((IssuerRef::Unknown, format), |issuer| {
+            (
+                issuer.into(),
+                match format {
+                    EntryFormat::Legacy => EntryFormat::LegacyWithIssuer,
+                    format => format,
+                },
+            )
+        });
+
+        Ok(Self {
+            timestamp,
+            issuer,
+            message: MessageRef::try_from(message)?,
+            format,
+        })
+    }
+
+    /// Returns the entry's timestamp.
+    #[must_use]
+    pub const fn timestamp(&self) -> DateTime<FixedOffset> {
+        self.timestamp
+    }
+
+    /// Returns the entry's issuer.
+    #[must_use]
+    pub const fn issuer(&self) -> IssuerRef<'a> {
+        self.issuer
+    }
+
+    /// Returns the entry's message.
+    #[must_use]
+    pub const fn message(&self) -> &MessageRef<'a> {
+        &self.message
+    }
+
+    /// Returns the format that the entry was written in.
+    #[must_use]
+    pub const fn format(&self) -> EntryFormat {
+        self.format
+    }
+
+    /// Convert into an owned [`Entry`].
+    #[must_use]
+    pub fn to_owned(&self) -> Entry {
+        Entry::new(
+            self.timestamp,
+            self.issuer.to_owned(),
+            self.message.to_owned(),
+            self.format,
+        )
+    }
+}
+
+impl<'a> TryFrom<&'a str> for EntryRef<'a> {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::parse_with_offset(s, Utc.fix())
+    }
+}
+
+impl From<EntryRef<'_>> for Entry {
+    fn from(entry: EntryRef<'_>) -> Self {
+        entry.to_owned()
+    }
+}
+
+fn parse_timestamp(
+    timestamp: &str,
+    offset: FixedOffset,
+) -> Result<(DateTime<FixedOffset>, EntryFormat), Error> {
+    if let Some(timestamp) = tokenizer::timestamp(timestamp) {
+        return Ok((timestamp, EntryFormat::Rfc3339));
+    }
+
+    if let Ok(timestamp) = NaiveDateTime::parse_from_str(timestamp, LEGACY_TIME_FORMAT) {
+        return timestamp
+            .and_local_timezone(offset)
+            .earliest()
+            .map(|timestamp| (timestamp, EntryFormat::Legacy))
+            .ok_or_else(|| Error::MalformedEntry(timestamp.to_string()));
+    }
+
+    Ok((
+        DateTime::parse_from_str(timestamp, TIME_FORMAT)?,
+        EntryFormat::Rfc3339,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, TimeZone};
+
+    use super::EntryRef;
+    use crate::{EntryFormat, Error, IssuerRef, MessageRef};
+
+    fn at(offset: i32, hour: u32, minute: u32, second: u32) -> chrono::DateTime<FixedOffset> {
+        FixedOffset::east_opt(offset)
+            .and_then(|offset| {
+                offset
+                    .with_ymd_and_hms(2016, 3, 4, hour, minute, second)
+                    .single()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_rfc3339_entry() {
+        let entry =
+            EntryRef::try_from("[2016-03-04T12:34:56+0100] [ALPM] transaction started").unwrap();
+        assert_eq!(entry.timestamp(), at(3600, 12, 34, 56));
+        assert_eq!(entry.issuer(), IssuerRef::Alpm);
+        assert_eq!(entry.message(), &MessageRef::TransactionStarted);
+        assert_eq!(entry.format(), EntryFormat::Rfc3339);
+    }
+
+    #[test]
+    fn parses_legacy_entry() {
+        let entry = EntryRef::try_from("[2016-03-04 12:34] removed vim (7.4.1386-1)").unwrap();
+        assert_eq!(entry.timestamp(), at(0, 12, 34, 0));
+        assert_eq!(entry.issuer(), IssuerRef::Unknown);
+        assert!(matches!(entry.message(), MessageRef::Removed(package) if package.name() == "vim"));
+        assert_eq!(entry.format(), EntryFormat::Legacy);
+    }
+
+    #[test]
+    fn parses_legacy_entry_with_issuer() {
+        let entry = EntryRef::parse_with_offset(
+            "[2016-03-04 12:34] [PACMAN] starting full system upgrade",
+            FixedOffset::east_opt(3600).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(entry.timestamp(), at(3600, 12, 34, 0));
+        assert_eq!(entry.issuer(), IssuerRef::Pacman);
+        assert_eq!(entry.message(), &MessageRef::StartingFullSystemUpgrade);
+        assert_eq!(entry.format(), EntryFormat::LegacyWithIssuer);
+    }
+
+    #[test]
+    fn borrows_from_line() {
+        let line = "[2016-03-04T12:34:56+0100] [ALPM] upgraded systemd (229-1 -> 229-3)";
+        let entry = EntryRef::try_from(line).unwrap();
+        let MessageRef::Upgraded(upgrade) = entry.message() else {
+            panic!("not an upgrade: {entry:?}");
+        };
+        assert!(line
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&upgrade.name().as_ptr()));
+        assert_eq!(upgrade.old_version(), "229-1");
+        assert_eq!(upgrade.new_version(), "229-3");
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(matches!(
+            EntryRef::try_from("==> Starting build: 4.4.5-1-ARCH"),
+            Err(Error::MalformedEntry(_))
+        ));
+        assert!(matches!(
+            EntryRef::try_from("[yesterday] [ALPM] transaction started"),
+            Err(Error::InvalidTimestamp(_))
+        ));
+        assert!(matches!(
+            EntryRef::try_from("[2016-03-04T12:34:56+0100] [ALPM] installed vim"),
+            Err(Error::InvalidMessage(_))
+        ));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::Issuer;
+
+/// Borrowed issuer of the log entry.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IssuerRef<'a> {
+    /// Entry was issued by `ALPM`.
+    Alpm,
+    /// Entry was issued by `ALPM Scriptlet`.
+    AlpmScriptlet,
+    /// Entry was issued by `pacman`.
+    Pacman,
+    /// Entry did not specify an issuer, as in legacy log files.
+    Unknown,
+    /// Entry was issued by something else.
+    Other(&'a str),
+}
+
+impl IssuerRef<'_> {
+    /// Convert into an owned [`Issuer`].
+    #[must_use]
+    pub fn to_owned(&self) -> Issuer {
+        match self {
+            Self::Alpm => Issuer::Alpm,
+            Self::AlpmScriptlet => Issuer::AlpmScriptlet,
+            Self::Pacman => Issuer::Pacman,
+            Self::Unknown => Issuer::Unknown,
+            Self::Other(issuer) => Issuer::Other((*issuer).to_string()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for IssuerRef<'a> {
+    fn from(s: &'a str) -> Self {
+        match s {
+            "ALPM" => Self::Alpm,
+            "ALPM-SCRIPTLET" => Self::AlpmScriptlet,
+            "PACMAN" => Self::Pacman,
+            _ => Self::Other(s),
+        }
+    }
+}
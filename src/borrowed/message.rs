@@ -0,0 +1,208 @@
+use std::str::FromStr;
+
+use crate::message::Error;
+use crate::{Command, Message};
+
+use super::{ConfigFileRef, DowngradeRef, PackageRef, UpgradeRef};
+
+/// Borrowed log messages.
+///
+/// Command lines are split eagerly, since unquoting their arguments requires allocations.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MessageRef<'a> {
+    /// Pacman has been invoked with a command line.
+    Running(Command),
+    /// The package databases are being synchronized.
+    SynchronizingPackageLists,
+    /// A full system upgrade has been started.
+    StartingFullSystemUpgrade,
+    /// A transaction has been started.
+    TransactionStarted,
+    /// A package has been installed.
+    Installed(PackageRef<'a>),
+    /// A package has been upgraded.
+    Upgraded(UpgradeRef<'a>),
+    /// A package has been downgraded.
+    Downgraded(DowngradeRef<'a>),
+    /// A package has been reinstalled.
+    Reinstalled(PackageRef<'a>),
+    /// A package has been removed.
+    Removed(PackageRef<'a>),
+    /// A transaction has been completed.
+    TransactionCompleted,
+    /// A transaction has been interrupted.
+    TransactionInterrupted,
+    /// A new configuration file has been installed as `.pacnew`, since the original one has been modified.
+    Pacnew(ConfigFileRef<'a>),
+    /// A modified configuration file has been saved as `.pacsave` while removing its package.
+    Pacsave(ConfigFileRef<'a>),
+    /// A warning has been issued.
+    Warning(&'a str),
+    /// An error has been issued.
+    Error(&'a str),
+    /// Other messages.
+    Other(&'a str),
+}
+
+impl MessageRef<'_> {
+    /// Convert into an owned [`Message`].
+    #[must_use]
+    pub fn to_owned(&self) -> Message {
+        match self {
+            Self::Running(command) => Message::Running(command.clone()),
+            Self::SynchronizingPackageLists => Message::SynchronizingPackageLists,
+            Self::StartingFullSystemUpgrade => Message::StartingFullSystemUpgrade,
+            Self::TransactionStarted => Message::TransactionStarted,
+            Self::Installed(package) => Message::Installed(package.to_owned()),
+            Self::Upgraded(upgrade) => Message::Upgraded(upgrade.to_owned()),
+            Self::Downgraded(downgrade) => Message::Downgraded(downgrade.to_owned()),
+            Self::Reinstalled(package) => Message::Reinstalled(package.to_owned()),
+            Self::Removed(package) => Message::Removed(package.to_owned()),
+            Self::TransactionCompleted => Message::TransactionCompleted,
+            Self::TransactionInterrupted => Message::TransactionInterrupted,
+            Self::Pacnew(config_file) => Message::Pacnew(config_file.to_owned()),
+            Self::Pacsave(config_file) => Message::Pacsave(config_file.to_owned()),
+            Self::Warning(text) => Message::Warning((*text).to_string()),
+            Self::Error(text) => Message::Error((*text).to_string()),
+            Self::Other(text) => Message::Other((*text).to_string()),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for MessageRef<'a> {
+    type Error = Error;
+
+    fn try_from(text: &'a str) -> Result<Self, Error> {
+        let mut parts = text.splitn(2, ' ');
+
+        let Some(command) = parts.next() else {
+            return Ok(Self::Other(text));
+        };
+
+        match command {
+            "Running" => Ok(parts
+                .next()
+                .and_then(|command| command.strip_prefix('\'')?.strip_suffix('\''))
+                .and_then(|command| Command::from_str(command).ok())
+                .map_or(Self::Other(text), Self::Running)),
+            "installed" => Ok(Self::Installed(PackageRef::try_from(
+                parts.next().ok_or(Error::MissingParameters)?,
+            )?)),
+            "upgraded" => Ok(Self::Upgraded(UpgradeRef::try_from(
+                parts.next().ok_or(Error::MissingParameters)?,
+            )?)),
+            "downgraded" => Ok(Self::Downgraded(DowngradeRef::try_from(
+                parts.next().ok_or(Error::MissingParameters)?,
+            )?)),
+            "reinstalled" => Ok(Self::Reinstalled(PackageRef::try_from(
+                parts.next().ok_or(Error::MissingParameters)?,
+            )?)),
+            "removed" => Ok(Self::Removed(PackageRef::try_from(
+                parts.next().ok_or(Error::MissingParameters)?,
+            )?)),
+            "warning:" => {
+                let warning = parts.next().unwrap_or_default();
+                Ok(ConfigFileRef::pacnew(warning)
+                    .map(Self::Pacnew)
+                    .or_else(|_| ConfigFileRef::pacsave(warning).map(Self::Pacsave))
+                    .unwrap_or(Self::Warning(warning)))
+            }
+            "error:" => Ok(Self::Error(parts.next().unwrap_or_default())),
+            _ => match text {
+                "synchronizing package lists" => Ok(Self::SynchronizingPackageLists),
+                "starting full system upgrade" => Ok(Self::StartingFullSystemUpgrade),
+                "transaction started" => Ok(Self::TransactionStarted),
+                "transaction completed" => Ok(Self::TransactionCompleted),
+                "transaction interrupted" => Ok(Self::TransactionInterrupted),
+                text => Ok(Self::Other(text)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::MessageRef;
+    use crate::Operation;
+
+    #[test]
+    fn parses_running() {
+        let MessageRef::Running(command) =
+            MessageRef::try_from("Running 'pacman -S --needed base-devel'").unwrap()
+        else {
+            panic!("not a command");
+        };
+        assert_eq!(command.operation(), Some(Operation::Sync));
+        assert!(command.has_flag("--needed"));
+        assert_eq!(command.targets(), ["base-devel"]);
+    }
+
+    #[test]
+    fn parses_running_with_unbalanced_quote() {
+        let MessageRef::Running(command) =
+            MessageRef::try_from("Running 'pacman -U /tmp/it's.pkg.tar.zst'").unwrap()
+        else {
+            panic!("not a command");
+        };
+        assert_eq!(command.targets(), ["/tmp/it's.pkg.tar.zst"]);
+    }
+
+    #[test]
+    fn keeps_unquoted_running_as_other() {
+        assert_eq!(
+            MessageRef::try_from("Running pacman -Syu").unwrap(),
+            MessageRef::Other("Running pacman -Syu")
+        );
+    }
+
+    #[test]
+    fn parses_package_events() {
+        assert!(matches!(
+            MessageRef::try_from("downgraded mesa (24.0.2-1 -> 23.3.5-1)").unwrap(),
+            MessageRef::Downgraded(downgrade) if downgrade.name() == "mesa"
+        ));
+        assert!(matches!(
+            MessageRef::try_from("reinstalled bash (5.2.026-2)").unwrap(),
+            MessageRef::Reinstalled(package) if package.version() == "5.2.026-2"
+        ));
+    }
+
+    #[test]
+    fn parses_config_files() {
+        let MessageRef::Pacnew(config_file) =
+            MessageRef::try_from("warning: /etc/pacman.conf installed as /etc/pacman.conf.pacnew")
+                .unwrap()
+        else {
+            panic!("not a pacnew file");
+        };
+        assert_eq!(config_file.original(), Path::new("/etc/pacman.conf"));
+        assert_eq!(config_file.new_path(), Path::new("/etc/pacman.conf.pacnew"));
+        assert!(matches!(
+            MessageRef::try_from(
+                "warning: /etc/ssh/sshd_config saved as /etc/ssh/sshd_config.pacsave"
+            )
+            .unwrap(),
+            MessageRef::Pacsave(_)
+        ));
+    }
+
+    #[test]
+    fn classifies_problems() {
+        assert_eq!(
+            MessageRef::try_from("warning: dbus: local (1.14.10-2) is newer than core (1.14.10-1)")
+                .unwrap(),
+            MessageRef::Warning("dbus: local (1.14.10-2) is newer than core (1.14.10-1)")
+        );
+        assert_eq!(
+            MessageRef::try_from("error: failed to commit transaction (conflicting files)")
+                .unwrap(),
+            MessageRef::Error("failed to commit transaction (conflicting files)")
+        );
+        assert_eq!(
+            MessageRef::try_from("running '30-systemd-update.hook'...").unwrap(),
+            MessageRef::Other("running '30-systemd-update.hook'...")
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use crate::tokenizer;
+use crate::Package;
+
+/// Borrowed information about a package.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PackageRef<'a> {
+    name: &'a str,
+    version: &'a str,
+}
+
+impl<'a> PackageRef<'a> {
+    /// Return the package's name.
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Return the package's version.
+    #[must_use]
+    pub const fn version(&self) -> &'a str {
+        self.version
+    }
+
+    /// Convert into an owned [`Package`].
+    #[must_use]
+    pub fn to_owned(&self) -> Package {
+        Package::new(self.name.to_string(), self.version.to_string())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PackageRef<'a> {
+    type Error = String;
+
+    fn try_from(text: &'a str) -> Result<Self, Self::Error> {
+        let (name, version) = tokenizer::package(text).ok_or_else(|| text.to_string())?;
+        Ok(Self { name, version })
+    }
+}
@@ -0,0 +1,54 @@
+use crate::tokenizer;
+use crate::Upgrade;
+
+/// Borrowed information about a package upgrade.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UpgradeRef<'a> {
+    name: &'a str,
+    old_version: &'a str,
+    new_version: &'a str,
+}
+
+impl<'a> UpgradeRef<'a> {
+    /// Return the name of the upgraded package.
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Return the old version of the package.
+    #[must_use]
+    pub const fn old_version(&self) -> &'a str {
+        self.old_version
+    }
+
+    /// Return the new version of the package.
+    #[must_use]
+    pub const fn new_version(&self) -> &'a str {
+        self.new_version
+    }
+
+    /// Convert into an owned [`Upgrade`].
+    #[must_use]
+    pub fn to_owned(&self) -> Upgrade {
+        Upgrade::new(
+            self.name.to_string(),
+            self.old_version.to_string(),
+            self.new_version.to_string(),
+        )
+    }
+}
+
+impl<'a> TryFrom<&'a str> for UpgradeRef<'a> {
+    type Error = String;
+
+    fn try_from(text: &'a str) -> Result<Self, Self::Error> {
+        let (name, old_version, new_version) =
+            tokenizer::version_change(text).ok_or_else(|| text.to_string())?;
+        Ok(Self {
+            name,
+            old_version,
+            new_version,
+        })
+    }
+}
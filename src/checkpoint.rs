@@ -0,0 +1,303 @@
+use std::fmt::Display;
+use std::fs::{metadata, read_to_string, write, Metadata};
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::index::inode;
+use crate::{Entry, TransactionSpan};
+
+const HEADER: &str = "pacmanlog-checkpoint 1";
+
+/// The position up to which a logfile has been processed.
+///
+/// Checkpoints are taken with [`LogReader::checkpoint`](crate::LogReader::checkpoint)
+/// and resumed from with [`LogReader::resume`](crate::LogReader::resume).
+/// They serialize to and from a short text representation via [`Display`] and [`FromStr`].
+///
+/// A checkpoint only retains the transaction that is still open, so transactions grouped with
+/// [`Transactions::resumed_transactions`](crate::Transactions::resumed_transactions) are yielded
+/// as soon as they are completed and lack the hooks, warnings and errors logged afterwards,
+/// unlike those grouped with [`Transactions::transactions`](crate::Transactions::transactions).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Checkpoint {
+    inode: u64,
+    offset: u64,
+    line: usize,
+    timestamp: Option<DateTime<FixedOffset>>,
+    context: Option<u64>,
+    transaction: Option<(u64, u64)>,
+}
+
+impl Checkpoint {
+    pub(crate) const fn new(
+        inode: u64,
+        offset: u64,
+        line: usize,
+        timestamp: Option<DateTime<FixedOffset>>,
+        context: Option<u64>,
+        transaction: Option<(u64, u64)>,
+    ) -> Self {
+        Self {
+            inode,
+            offset,
+            line,
+            timestamp,
+            context,
+            transaction,
+        }
+    }
+
+    /// Load a checkpoint from the given file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file could not be read or is malformed.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        read_to_string(path)?.parse()
+    }
+
+    /// Save the checkpoint to the given file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the file could not be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        write(path, self.to_string())
+    }
+
+    /// Return the inode of the logfile.
+    #[must_use]
+    pub const fn inode(&self) -> u64 {
+        self.inode
+    }
+
+    /// Return the byte offset up to which the logfile has been processed.
+    #[must_use]
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Return the amount of lines preceding the byte offset.
+    ///
+    /// Line numbers of [`ReadError`](crate::ReadError)s after resuming continue from it.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the timestamp of the last processed entry, if any.
+    #[must_use]
+    pub const fn timestamp(&self) -> Option<DateTime<FixedOffset>> {
+        self.timestamp
+    }
+
+    /// Return the byte offset of the command or full system upgrade announcement
+    /// preceding a transaction that has not yet been started, if any.
+    #[must_use]
+    pub const fn context(&self) -> Option<u64> {
+        self.context
+    }
+
+    /// Return whether a transaction had been started but neither completed nor interrupted at the checkpoint.
+    #[must_use]
+    pub const fn is_transaction_open(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    /// Return the byte range of the open transaction up to the checkpoint, if any.
+    #[must_use]
+    pub const fn transaction(&self) -> Option<TransactionSpan> {
+        match self.transaction {
+            Some((context, start)) => Some(TransactionSpan::new(context, start, self.offset)),
+            None => None,
+        }
+    }
+
+    /// Return whether the checkpoint applies to the logfile with the given metadata.
+    ///
+    /// This is not the case if the logfile has been replaced or truncated since the checkpoint was taken.
+    pub(crate) fn applies_to(&self, metadata: &Metadata) -> bool {
+        inode(metadata) == self.inode && metadata.len() >= self.offset
+    }
+
+    /// Read the entries of the open transaction or the context of the next transaction
+    /// that precede the checkpoint from the given logfile.
+    ///
+    /// If the logfile has been replaced or truncated since the checkpoint was taken,
+    /// no entries are returned, since [`LogReader::resume`](crate::LogReader::resume)
+    /// reads it from the beginning.
+    ///
+    /// Pass them to [`Transactions::resumed_transactions`](crate::Transactions::resumed_transactions)
+    /// so that a transaction spanning the checkpoint is yielded as a whole.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the logfile could not be read.
+    pub fn replay(&self, path: impl AsRef<Path>) -> std::io::Result<Vec<Entry>> {
+        if !self.applies_to(&metadata(path.as_ref())?) {
+            return Ok(Vec::new());
+        }
+
+        self.transaction()
+            .or_else(|| {
+                self.context
+                    .map(|context| TransactionSpan::new(context, self.offset, self.offset))
+            })
+            .map_or_else(|| Ok(Vec::new()), |span| span.entries(path.as_ref()))
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "file {} {}", self.inode, self.offset)?;
+        writeln!(f, "line {}", self.line)?;
+
+        if let Some(timestamp) = self.timestamp {
+            writeln!(f, "timestamp {}", timestamp.to_rfc3339())?;
+        }
+
+        if let Some(context) = self.context {
+            writeln!(f, "context {context}")?;
+        }
+
+        if let Some((context, start)) = self.transaction {
+            writeln!(f, "transaction {context} {start}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err(invalid_data("missing checkpoint header"));
+        }
+
+        let mut checkpoint = Self::new(0, 0, 0, None, None, None);
+        let mut file = false;
+
+        for line in lines {
+            let (key, value) = line.split_once(' ').ok_or_else(|| invalid_data(line))?;
+
+            match key {
+                "file" => {
+                    let (inode, offset) = parse_pair(value)?;
+                    checkpoint.inode = inode;
+                    checkpoint.offset = offset;
+                    file = true;
+                }
+                "line" => checkpoint.line = value.parse().map_err(|_| invalid_data(value))?,
+                "timestamp" => {
+                    checkpoint.timestamp =
+                        Some(DateTime::parse_from_rfc3339(value).map_err(|_| invalid_data(value))?);
+                }
+                "context" => {
+                    checkpoint.context = Some(value.parse().map_err(|_| invalid_data(value))?);
+                }
+                "transaction" => checkpoint.transaction = Some(parse_pair(value)?),
+                _ => return Err(invalid_data(line)),
+            }
+        }
+
+        if file {
+            Ok(checkpoint)
+        } else {
+            Err(invalid_data("missing file position"))
+        }
+    }
+}
+
+fn parse_pair(text: &str) -> std::io::Result<(u64, u64)> {
+    let (first, second) = text.split_once(' ').ok_or_else(|| invalid_data(text))?;
+    Ok((
+        first.parse().map_err(|_| invalid_data(first))?,
+        second.parse().map_err(|_| invalid_data(second))?,
+    ))
+}
+
+fn invalid_data(text: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("malformed checkpoint: {text}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Checkpoint;
+    use crate::fixture::{TempDir, LOG};
+    use crate::{LogReader, Transactions};
+
+    #[test]
+    fn resumes_at_every_cut_point() {
+        let dir = TempDir::new("checkpoint-cut");
+        let path = dir.write("pacman.log", LOG);
+        let expected: Vec<_> = LogReader::new(LOG.as_bytes())
+            .map(Result::unwrap)
+            .streaming_transactions()
+            .collect();
+        let count = LogReader::new(LOG.as_bytes()).count();
+
+        for cut in 0..=count {
+            let mut reader = LogReader::open(&path).unwrap();
+            let mut transactions: Vec<_> = reader
+                .by_ref()
+                .take(cut)
+                .map(Result::unwrap)
+                .resumed_transactions([])
+                .collect();
+            let checkpoint: Checkpoint = reader.checkpoint().unwrap().to_string().parse().unwrap();
+
+            let replay = checkpoint.replay(&path).unwrap();
+            transactions.extend(
+                LogReader::resume(&path, &checkpoint)
+                    .unwrap()
+                    .map(Result::unwrap)
+                    .resumed_transactions(replay),
+            );
+            assert_eq!(transactions, expected, "cut after {cut} entries");
+        }
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let dir = TempDir::new("checkpoint-text");
+        let path = dir.write("pacman.log", LOG);
+        let mut reader = LogReader::open(&path).unwrap();
+        reader.by_ref().take(6).for_each(drop);
+
+        let checkpoint = reader.checkpoint().unwrap();
+        assert!(checkpoint.is_transaction_open());
+        assert!(checkpoint.timestamp().is_some());
+        assert_eq!(
+            checkpoint.to_string().parse::<Checkpoint>().unwrap(),
+            checkpoint
+        );
+        assert!("pacmanlog-checkpoint 1\nline 3\n"
+            .parse::<Checkpoint>()
+            .is_err());
+    }
+
+    #[test]
+    fn starts_over_when_the_logfile_has_been_truncated() {
+        let dir = TempDir::new("checkpoint-truncated");
+        let path = dir.write("pacman.log", LOG);
+        let mut reader = LogReader::open(&path).unwrap();
+        reader.by_ref().take(6).for_each(drop);
+        let checkpoint = reader.checkpoint().unwrap();
+
+        let rotated = "[2024-03-05T00:00:00+0100] [ALPM] transaction started\n";
+        dir.write("pacman.log", rotated);
+        assert!(checkpoint.replay(&path).unwrap().is_empty());
+        assert_eq!(LogReader::resume(&path, &checkpoint).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,89 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const BZIP2_MAGIC: &[u8] = b"BZh";
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const XZ_MAGIC: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Open a possibly compressed file, detecting the compression by its magic bytes.
+///
+/// # Errors
+///
+/// Returns an [`std::io::Error`] if the file could not be opened or its compression is not supported.
+pub fn open(path: &Path) -> std::io::Result<Box<dyn BufRead>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let magic = reader.fill_buf()?;
+
+    if magic.starts_with(BZIP2_MAGIC) {
+        bzip2(reader)
+    } else if magic.starts_with(GZIP_MAGIC) {
+        gzip(reader)
+    } else if magic.starts_with(XZ_MAGIC) {
+        xz(reader)
+    } else if magic.starts_with(ZSTD_MAGIC) {
+        zstd(reader)
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+#[cfg(feature = "bzip2")]
+#[allow(clippy::unnecessary_wraps)]
+fn bzip2(reader: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Ok(Box::new(BufReader::new(
+        bzip2::bufread::MultiBzDecoder::new(reader),
+    )))
+}
+
+#[cfg(not(feature = "bzip2"))]
+fn bzip2(_: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Err(unsupported("bzip2"))
+}
+
+#[cfg(feature = "gzip")]
+#[allow(clippy::unnecessary_wraps)]
+fn gzip(reader: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Ok(Box::new(BufReader::new(
+        flate2::bufread::MultiGzDecoder::new(reader),
+    )))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gzip(_: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Err(unsupported("gzip"))
+}
+
+#[cfg(feature = "xz")]
+#[allow(clippy::unnecessary_wraps)]
+fn xz(reader: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Ok(Box::new(BufReader::new(
+        xz2::bufread::XzDecoder::new_multi_decoder(reader),
+    )))
+}
+
+#[cfg(not(feature = "xz"))]
+fn xz(_: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Err(unsupported("xz"))
+}
+
+#[cfg(feature = "zstd")]
+fn zstd(reader: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Ok(Box::new(BufReader::new(zstd::Decoder::with_buffer(
+        reader,
+    )?)))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd(_: BufReader<File>) -> std::io::Result<Box<dyn BufRead>> {
+    Err(unsupported("zstd"))
+}
+
+#[cfg(not(all(feature = "bzip2", feature = "gzip", feature = "xz", feature = "zstd")))]
+fn unsupported(compression: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("{compression} compression requires the `{compression}` feature"),
+    )
+}
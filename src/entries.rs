@@ -0,0 +1,19 @@
+use crate::EntriesIterator;
+
+/// Parse lines into entries.
+pub trait Entries: Iterator<Item = String> + Sized {
+    /// Return an iterator of [`Entry`](crate::Entry)s.
+    ///
+    /// Lines without a `[timestamp]` prefix are considered continuation lines of
+    /// the previous entry and are appended to its message body.
+    fn entries(self) -> EntriesIterator<Self>;
+}
+
+impl<T> Entries for T
+where
+    T: Iterator<Item = String>,
+{
+    fn entries(self) -> EntriesIterator<Self> {
+        EntriesIterator::from(self)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::assembler::{Assembler, Push};
+use crate::{Entry, Error};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EntriesIterator<T>
+where
+    T: Iterator<Item = String>,
+{
+    lines: T,
+    assembler: Assembler,
+    queued: Option<Error>,
+}
+
+impl<T> From<T> for EntriesIterator<T>
+where
+    T: Iterator<Item = String>,
+{
+    fn from(lines: T) -> Self {
+        Self {
+            lines,
+            assembler: Assembler::default(),
+            queued: None,
+        }
+    }
+}
+
+impl<T> Iterator for EntriesIterator<T>
+where
+    T: Iterator<Item = String>,
+{
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.queued.take() {
+            return Some(Err(error));
+        }
+
+        loop {
+            let Some(line) = self.lines.next() else {
+                return self.assembler.finish().map(Ok);
+            };
+
+            match self.assembler.push(&line) {
+                Push::Started(Some(entry)) => return Some(Ok(entry)),
+                Push::Started(None) | Push::Continued => {}
+                Push::Failed(error) => {
+                    if let Some(entry) = self.assembler.finish() {
+                        self.queued.replace(error);
+                        return Some(Ok(entry));
+                    }
+
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,14 @@
-use std::cell::LazyCell;
 use std::str::FromStr;
 
-use chrono::{DateTime, FixedOffset};
-use regex::Regex;
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+
+pub use format::EntryFormat;
 
 use crate::error::Error;
 use crate::message::Message;
-use crate::Issuer;
+use crate::{EntryRef, Issuer};
 
-const REGEX_STR: &str = r"\[(.+)\] \[(.+)\] (.+)";
-#[allow(clippy::declare_interior_mutable_const)]
-const REGEX: LazyCell<Regex> = LazyCell::new(|| Regex::new(REGEX_STR).expect("malformed regex"));
-const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%z";
+mod format;
 
 /// A log file entry.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -19,9 +16,37 @@ pub struct Entry {
     timestamp: DateTime<FixedOffset>,
     issuer: Issuer,
     message: Message,
+    format: EntryFormat,
 }
 
 impl Entry {
+    /// Parse an entry, assuming the given offset for timestamps without a time zone.
+    ///
+    /// [`Entry::from_str`] assumes UTC for such timestamps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the entry could not be parsed.
+    pub fn from_str_with_offset(s: &str, offset: FixedOffset) -> Result<Self, Error> {
+        EntryRef::parse_with_offset(s, offset).map(|entry| entry.to_owned())
+    }
+
+    /// Create a new entry.
+    #[must_use]
+    pub(crate) const fn new(
+        timestamp: DateTime<FixedOffset>,
+        issuer: Issuer,
+        message: Message,
+        format: EntryFormat,
+    ) -> Self {
+        Self {
+            timestamp,
+            issuer,
+            message,
+            format,
+        }
+    }
+
     /// Returns the entry's timestamp.
     #[must_use]
     pub const fn timestamp(&self) -> DateTime<FixedOffset> {
@@ -39,23 +64,31 @@ impl Entry {
     pub const fn message(&self) -> &Message {
         &self.message
     }
+
+    /// Consume the entry and return its message.
+    #[must_use]
+    pub fn into_message(self) -> Message {
+        self.message
+    }
+
+    /// Append a continuation line to the entry's message body.
+    ///
+    /// Returns `false` if the message cannot hold continuation lines.
+    pub(crate) fn append_line(&mut self, line: &str) -> bool {
+        self.message.append_line(line)
+    }
+
+    /// Returns the format that the entry was written in.
+    #[must_use]
+    pub const fn format(&self) -> EntryFormat {
+        self.format
+    }
 }
 
 impl FromStr for Entry {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        #[allow(clippy::borrow_interior_mutable_const)]
-        let (_, [timestamp, issuer, message]) = REGEX
-            .captures_iter(s)
-            .map(|capture| capture.extract())
-            .next()
-            .ok_or_else(|| Error::MalformedEntry(s.to_string()))?;
-
-        Ok(Self {
-            timestamp: DateTime::parse_from_str(timestamp, TIME_FORMAT)?,
-            issuer: issuer.to_string().into(),
-            message: Message::from_str(message)?,
-        })
+        Self::from_str_with_offset(s, Utc.fix())
     }
 }
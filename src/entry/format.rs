@@ -0,0 +1,10 @@
+/// Formats of log entries written by different pacman versions.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum EntryFormat {
+    /// `[2016-03-04 12:34] message`, written by the oldest pacman versions.
+    Legacy,
+    /// `[2016-03-04 12:34] [ISSUER] message`, written by pacman before 5.x.
+    LegacyWithIssuer,
+    /// `[2019-03-04T12:34:56+0100] [ISSUER] message`, written by current pacman versions.
+    Rfc3339,
+}
@@ -0,0 +1,72 @@
+//! Fixtures shared by the unit tests.
+use std::env::temp_dir;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+use std::process::id;
+
+/// A log of several invocations of pacman, including an aborted and an interrupted one.
+pub const LOG: &str = "\
+[2024-03-01T09:00:00+0100] [PACMAN] Running 'pacman -Syu'
+[2024-03-01T09:00:01+0100] [PACMAN] synchronizing package lists
+[2024-03-01T09:00:02+0100] [PACMAN] starting full system upgrade
+[2024-03-01T09:00:03+0100] [ALPM] running '60-mkinitcpio-remove.hook'...
+[2024-03-01T09:00:04+0100] [ALPM] transaction started
+[2024-03-01T09:00:05+0100] [ALPM] upgraded linux (6.7.8.arch1-1 -> 6.7.9.arch1-1)
+[2024-03-01T09:00:06+0100] [ALPM] upgraded glibc (2.39-1 -> 2.39-2)
+[2024-03-01T09:00:06+0100] [ALPM-SCRIPTLET] Generating locales...
+  en_US.UTF-8... done
+[2024-03-01T09:00:07+0100] [ALPM] warning: /etc/locale.gen installed as /etc/locale.gen.pacnew
+[2024-03-01T09:00:08+0100] [ALPM] transaction completed
+[2024-03-01T09:00:09+0100] [ALPM] running '90-mkinitcpio-install.hook'...
+[2024-03-01T09:00:10+0100] [ALPM-SCRIPTLET] ==> Building image from preset: /etc/mkinitcpio.d/linux.preset: 'default'
+[2024-03-02T10:00:00+0100] [PACMAN] Running 'pacman -S linux'
+[2024-03-02T10:00:01+0100] [ALPM] running '10-abort.hook'...
+[2024-03-02T10:00:02+0100] [ALPM] error: command failed to execute correctly
+[2024-03-02T10:05:00+0100] [PACMAN] Running 'pacman -S firefox'
+[2024-03-02T10:05:01+0100] [ALPM] transaction started
+[2024-03-02T10:05:02+0100] [ALPM] installed nss (3.98-1)
+[2024-03-02T10:05:03+0100] [ALPM] transaction interrupted
+[2024-03-02T10:06:00+0100] [PACMAN] Running 'pacman -S firefox'
+[2024-03-02T10:06:01+0100] [ALPM] warning: nss-3.98-1 is up to date -- reinstalling
+[2024-03-02T10:06:02+0100] [ALPM] transaction started
+[2024-03-02T10:06:03+0100] [ALPM] reinstalled nss (3.98-1)
+[2024-03-02T10:06:04+0100] [ALPM] installed firefox (123.0.1-1)
+[2024-03-02T10:06:05+0100] [PACMAN] checking keyring
+[2024-03-02T10:06:05+0100] [ALPM] some unrecognized note
+[2024-03-02T10:06:06+0100] [ALPM] transaction completed
+[2024-03-03T11:00:00+0100] [PACMAN] Running 'pacman -Rs vim'
+[2024-03-03T11:00:01+0100] [ALPM] transaction started
+[2024-03-03T11:00:02+0100] [ALPM] removed vim (9.1.0142-1)
+[2024-03-03T11:00:02+0100] [ALPM] warning: /etc/vimrc saved as /etc/vimrc.pacsave
+[2024-03-03T11:00:03+0100] [ALPM] transaction completed
+[2024-03-04T12:00:00+0100] [PACMAN] Running 'pacman -Sy python'
+[2024-03-04T12:00:01+0100] [PACMAN] synchronizing package lists
+[2024-03-04T12:00:02+0100] [ALPM] transaction started
+[2024-03-04T12:00:03+0100] [ALPM] downgraded python (3.12.2-1 -> 3.11.8-1)
+[2024-03-04T12:00:04+0100] [ALPM] transaction completed
+";
+
+/// A temporary directory that is removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create a new temporary directory, named uniquely per process and test.
+    pub fn new(name: &str) -> Self {
+        let path = temp_dir().join(format!("pacmanlog-{}-{name}", id()));
+        create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    /// Write a file into the directory and return its path.
+    pub fn write(&self, name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.0.join(name);
+        write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+use std::fs::{metadata, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use chrono::FixedOffset;
+
+use crate::assembler::{Assembler, Push};
+use crate::index::inode;
+use crate::reader::trim_newline;
+use crate::{Entry, ReadError, ReadErrorKind};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Follows a logfile like `tail -F`, yielding new entries as they are written.
+///
+/// Truncation of the file is detected by its size shrinking and rotation by its inode changing,
+/// on platforms that have inodes.
+/// In both cases the file is read again from its start.
+/// Since continuation lines may still be written, the last entry is only yielded once the next one starts,
+/// the file is rotated or truncated, or nothing has been written for the flush timeout.
+/// The iterator blocks while waiting for new entries and never ends on its own.
+#[derive(Debug)]
+pub struct Follow {
+    path: PathBuf,
+    reader: BufReader<File>,
+    inode: u64,
+    offset: u64,
+    line: usize,
+    buffer: Vec<u8>,
+    assembler: Assembler,
+    queued: VecDeque<Result<Entry, ReadError>>,
+    interval: Duration,
+    flush_timeout: Duration,
+    last_read: Instant,
+}
+
+impl Follow {
+    /// Follow the logfile at the given path, starting at its current end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be opened.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut follow = Self::open_from_start(path)?;
+        follow.offset = follow.reader.seek(SeekFrom::End(0))?;
+        Ok(follow)
+    }
+
+    /// Follow the logfile at the given path, starting at its beginning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be opened.
+    pub fn open_from_start(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+        let inode = inode(&file.metadata()?);
+        Ok(Self {
+            path,
+            reader: BufReader::new(file),
+            inode,
+            offset: 0,
+            line: 0,
+            buffer: Vec::new(),
+            assembler: Assembler::default(),
+            queued: VecDeque::new(),
+            interval: DEFAULT_INTERVAL,
+            flush_timeout: DEFAULT_FLUSH_TIMEOUT,
+            last_read: Instant::now(),
+        })
+    }
+
+    /// Set the interval at which the file is polled for changes.
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the time without new lines after which the last entry is yielded.
+    #[must_use]
+    pub const fn with_flush_timeout(mut self, timeout: Duration) -> Self {
+        self.flush_timeout = timeout;
+        self
+    }
+
+    /// Assume the given offset for timestamps without a time zone.
+    #[must_use]
+    pub fn with_default_offset(mut self, offset: FixedOffset) -> Self {
+        self.assembler = Assembler::new(offset);
+        self
+    }
+
+    /// Process the complete line in the buffer.
+    fn process_line(&mut self) {
+        self.line += 1;
+        let start = self.offset - self.buffer.len() as u64;
+        let bytes = trim_newline(&self.buffer);
+        let text = String::from_utf8_lossy(bytes);
+
+        match self.assembler.push(&text) {
+            Push::Started(Some(entry)) => self.queued.push_back(Ok(entry)),
+            Push::Started(None) | Push::Continued => {}
+            Push::Failed(error) => {
+                if let Some(entry) = self.assembler.finish() {
+                    self.queued.push_back(Ok(entry));
+                }
+
+                self.queued.push_back(Err(ReadError::new(
+                    self.line,
+                    start,
+                    bytes.to_vec(),
+                    ReadErrorKind::Parse(error),
+                )));
+            }
+        }
+
+        self.buffer.clear();
+    }
+
+    /// Reopen the file if it has been rotated or rewind it if it has been truncated.
+    ///
+    /// Returns `true` if the file has changed.
+    fn check_file(&mut self) -> std::io::Result<bool> {
+        let Ok(metadata) = metadata(&self.path) else {
+            return Ok(false);
+        };
+
+        if inode(&metadata) != self.inode {
+            let file = File::open(&self.path)?;
+            self.inode = inode(&file.metadata()?);
+            self.reader = BufReader::new(file);
+        } else if metadata.len() < self.offset {
+            self.reader.seek(SeekFrom::Start(0))?;
+        } else {
+            return Ok(false);
+        }
+
+        self.offset = 0;
+        self.line = 0;
+        self.buffer.clear();
+        Ok(true)
+    }
+}
+
+impl Iterator for Follow {
+    type Item = Result<Entry, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.queued.pop_front() {
+                return Some(item);
+            }
+
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => match self.check_file() {
+                    Ok(true) => {
+                        if let Some(entry) = self.assembler.finish() {
+                            return Some(Ok(entry));
+                        }
+                    }
+                    Ok(false) => {
+                        if self.last_read.elapsed() >= self.flush_timeout {
+                            if let Some(entry) = self.assembler.finish() {
+                                return Some(Ok(entry));
+                            }
+                        }
+
+                        sleep(self.interval);
+                    }
+                    Err(error) => {
+                        sleep(self.interval);
+                        return Some(Err(ReadError::new(
+                            self.line + 1,
+                            self.offset,
+                            Vec::new(),
+                            ReadErrorKind::Io(error),
+                        )));
+                    }
+                },
+                Ok(size) => {
+                    self.offset += size as u64;
+                    self.last_read = Instant::now();
+
+                    if self.buffer.ends_with(b"\n") {
+                        self.process_line();
+                    }
+                }
+                Err(error) => {
+                    sleep(self.interval);
+                    return Some(Err(ReadError::new(
+                        self.line + 1,
+                        self.offset,
+                        self.buffer.clone(),
+                        ReadErrorKind::Io(error),
+                    )));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{Entry, Transaction};
+
+/// A transaction or an entry that could not be assigned to one.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Grouped {
+    /// A transaction.
+    Transaction(Box<Transaction>),
+    /// An entry that has been logged outside of a transaction, such as a package event or an error.
+    Orphan(Entry),
+}
+
+impl Grouped {
+    /// Return the transaction, if any.
+    #[must_use]
+    pub const fn transaction(&self) -> Option<&Transaction> {
+        match self {
+            Self::Transaction(transaction) => Some(transaction),
+            Self::Orphan(_) => None,
+        }
+    }
+
+    /// Return the orphaned entry, if any.
+    #[must_use]
+    pub const fn orphan(&self) -> Option<&Entry> {
+        match self {
+            Self::Transaction(_) => None,
+            Self::Orphan(entry) => Some(entry),
+        }
+    }
+}
+
+impl From<Transaction> for Grouped {
+    fn from(transaction: Transaction) -> Self {
+        Self::Transaction(Box::new(transaction))
+    }
+}
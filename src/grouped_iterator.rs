@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+
+use crate::grouper::Grouper;
+use crate::{Grouped, IntoEntry, Transaction};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupedIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    entries: T,
+    grouper: Grouper,
+    queue: VecDeque<Grouped>,
+    done: bool,
+}
+
+impl<T> GroupedIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    /// Queue the given transaction, if any, followed by the orphans recorded before it was emitted.
+    ///
+    /// Orphans recorded after a transaction that has not yet been emitted are held back until it is.
+    fn queue(&mut self, transaction: Option<Transaction>) {
+        let emitted = transaction.is_some();
+        self.queue.extend(transaction.map(Grouped::from));
+
+        if emitted || !self.grouper.is_pending() {
+            self.queue
+                .extend(self.grouper.take_orphans().into_iter().map(Grouped::Orphan));
+        }
+    }
+}
+
+impl<T> From<T> for GroupedIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    fn from(entries: T) -> Self {
+        Self {
+            entries,
+            grouper: Grouper::default().keep_orphans(),
+            queue: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+impl<T> Iterator for GroupedIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    type Item = Grouped;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(grouped) = self.queue.pop_front() {
+                return Some(grouped);
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let Some(entry) = self.entries.next() else {
+                self.done = true;
+                let transaction = self.grouper.finish();
+                self.queue(transaction);
+                continue;
+            };
+
+            if !entry.is_groupable() {
+                self.grouper.keep_orphan(entry.into());
+                self.queue(None);
+                continue;
+            }
+
+            let transaction = self.grouper.push(entry.into());
+            self.queue(transaction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::fixture;
+    use crate::{Entry, Grouped, LogReader, Message, Transaction, Transactions};
+
+    const LOG: &str = "\
+[2024-03-09T07:59:00+0100] [ALPM] installed ancient (1.0-1)
+[2024-03-09T08:00:00+0100] [PACMAN] Running 'pacman -Syu'
+[2024-03-09T08:00:01+0100] [PACMAN] synchronizing package lists
+[2024-03-09T08:00:02+0100] [PACMAN] starting full system upgrade
+[2024-03-09T08:00:03+0100] [ALPM] running '10-abort.hook'...
+[2024-03-09T08:00:03+0100] [ALPM-SCRIPTLET] refusing to upgrade the running kernel
+[2024-03-09T08:00:04+0100] [ALPM] error: command failed to execute correctly
+[2024-03-09T08:00:05+0100] [PACMAN] free text logged by pacman
+[2024-03-09T08:00:06+0100] [ALPM] warning: /etc/pacman.conf installed as /etc/pacman.conf.pacnew
+[2024-03-09T08:01:00+0100] [PACMAN] Running 'pacman -S postgresql'
+[2024-03-09T08:01:01+0100] [ALPM] warning: postgresql-16.2-1 is up to date -- reinstalling
+[2024-03-09T08:01:02+0100] [ALPM] transaction started
+[2024-03-09T08:01:03+0100] [ALPM] reinstalled postgresql (16.2-1)
+[2024-03-09T08:01:03+0100] [ALPM-SCRIPTLET] Initialize the database cluster with initdb.
+[2024-03-09T08:01:04+0100] [ALPM-SCRIPTLET] error: could not reload postgresql.service
+[2024-03-09T08:01:05+0100] [ALPM] free text logged by ALPM
+[2024-03-09T08:01:05+0100] [PACMAN] more free text logged by pacman
+[2024-03-09T08:01:06+0100] [ALPM] warning: /etc/postgresql.conf installed as /etc/postgresql.conf.pacnew
+[2024-03-09T08:01:07+0100] [ALPM] transaction completed
+[2024-03-09T08:01:08+0100] [ALPM] running '30-systemd-update.hook'...
+[2024-03-09T08:01:09+0100] [ALPM] transaction completed
+";
+
+    /// Return the number of distinct entries that make up the transaction.
+    ///
+    /// Package events and config files are counted separately, since they do not keep their entries.
+    fn count(transaction: &Transaction) -> usize {
+        let mut entries: HashSet<&Entry> = transaction
+            .command_entry()
+            .into_iter()
+            .chain(transaction.sync())
+            .chain(transaction.full_system_upgrade())
+            .chain([transaction.start()])
+            .chain(transaction.completion())
+            .chain(transaction.interruption())
+            .chain(transaction.problems())
+            .chain(transaction.other())
+            .collect();
+        let mut events = transaction.pacnew_files().len() + transaction.pacsave_files().len();
+
+        for hook in transaction.hooks() {
+            entries.insert(hook.entry());
+            entries.extend(hook.output());
+        }
+
+        for event in transaction.installed() {
+            events += 1;
+            entries.extend(event.scriptlet_output());
+        }
+
+        for event in transaction.upgraded() {
+            events += 1;
+            entries.extend(event.scriptlet_output());
+        }
+
+        for event in transaction.downgraded() {
+            events += 1;
+            entries.extend(event.scriptlet_output());
+        }
+
+        for event in transaction.reinstalled() {
+            events += 1;
+            entries.extend(event.scriptlet_output());
+        }
+
+        for event in transaction.removed() {
+            events += 1;
+            entries.extend(event.scriptlet_output());
+        }
+
+        entries.len() + events
+    }
+
+    #[test]
+    fn loses_no_entries() {
+        let grouped: Vec<_> = LogReader::new(LOG.as_bytes())
+            .map(Result::unwrap)
+            .grouped()
+            .collect();
+
+        let transactions: Vec<_> = grouped.iter().filter_map(Grouped::transaction).collect();
+        assert_eq!(transactions.len(), 1);
+
+        let orphans = grouped.iter().filter_map(Grouped::orphan).count();
+        assert_eq!(orphans, 11);
+        assert_eq!(
+            transactions.into_iter().map(count).sum::<usize>() + orphans,
+            LOG.lines().count()
+        );
+    }
+
+    #[test]
+    fn yields_orphans_after_the_preceding_transaction() {
+        let grouped: Vec<_> = LogReader::new(fixture::LOG.as_bytes())
+            .map(Result::unwrap)
+            .grouped()
+            .collect();
+        let shape: String = grouped
+            .iter()
+            .map(|grouped| match grouped {
+                Grouped::Transaction(_) => 'T',
+                Grouped::Orphan(_) => 'O',
+            })
+            .collect();
+        assert_eq!(shape, "TOOOTTOTT");
+        assert!(matches!(
+            grouped[1].orphan().unwrap().message(),
+            Message::Running(_)
+        ));
+        assert_eq!(
+            grouped[6].orphan().unwrap().message(),
+            &Message::Other("checking keyring".to_string())
+        );
+        assert!(grouped
+            .iter()
+            .filter_map(Grouped::transaction)
+            .eq(&LogReader::new(fixture::LOG.as_bytes())
+                .map(Result::unwrap)
+                .transactions()
+                .collect::<Vec<_>>()));
+    }
+}
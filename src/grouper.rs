@@ -0,0 +1,691 @@
+use std::mem::take;
+
+use crate::{ConfigFile, Downgrade, Message, Upgrade};
+use crate::{Entry, Hook, HookPhase, Issuer, Package, PackageEvent, Transaction};
+use log::warn;
+
+/// State machine that groups entries into transactions.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Grouper {
+    eager: bool,
+    keep_orphans: bool,
+    pending_command: Option<Entry>,
+    command: Option<Entry>,
+    pending_sync: Option<Entry>,
+    sync: Option<Entry>,
+    outstanding_sync: Option<Entry>,
+    last_sync: Option<Entry>,
+    pending_full_system_upgrade: Option<Entry>,
+    full_system_upgrade: Option<Entry>,
+    start: Option<Entry>,
+    completion: Option<Entry>,
+    interruption: Option<Entry>,
+    installed: Vec<PackageEvent<Package>>,
+    upgraded: Vec<PackageEvent<Upgrade>>,
+    downgraded: Vec<PackageEvent<Downgrade>>,
+    reinstalled: Vec<PackageEvent<Package>>,
+    removed: Vec<PackageEvent<Package>>,
+    hooks: Vec<Hook>,
+    pending_hooks: Vec<Hook>,
+    pacnew: Vec<ConfigFile>,
+    pacsave: Vec<ConfigFile>,
+    problems: Vec<Entry>,
+    other: Vec<Entry>,
+    pending_entries: Vec<Entry>,
+    output: Output,
+    emitted: bool,
+    orphans: Vec<Entry>,
+}
+
+/// The hook or package event that scriptlet output is attached to.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+enum Output {
+    #[default]
+    None,
+    Pending,
+    Current,
+    Installed,
+    Upgraded,
+    Downgraded,
+    Reinstalled,
+    Removed,
+}
+
+impl Grouper {
+    /// Create a new grouper.
+    ///
+    /// If `eager` is `true`, transactions are emitted as soon as their completion entry is pushed.
+    /// Otherwise they are emitted once the next transaction starts.
+    pub fn new(eager: bool) -> Self {
+        Self {
+            eager,
+            ..Self::default()
+        }
+    }
+
+    /// Keep entries outside of transactions as orphans instead of discarding them.
+    pub const fn keep_orphans(mut self) -> Self {
+        self.keep_orphans = true;
+        self
+    }
+
+    /// Return whether a transaction has been started but not yet emitted.
+    pub const fn is_pending(&self) -> bool {
+        self.start.is_some()
+    }
+
+    /// Take the orphaned entries recorded so far.
+    pub fn take_orphans(&mut self) -> Vec<Entry> {
+        take(&mut self.orphans)
+    }
+
+    /// Keep an entry as an orphan if orphans are kept.
+    pub fn keep_orphan(&mut self, entry: Entry) {
+        if self.keep_orphans {
+            self.orphans.push(entry);
+        }
+    }
+
+    /// Push an entry, returning a transaction if one has been finished.
+    pub fn push(&mut self, entry: Entry) -> Option<Transaction> {
+        if matches!(entry.message(), Message::Other(_)) {
+            self.push_other(entry);
+            return None;
+        }
+
+        if matches!(entry.message(), Message::Warning(_) | Message::Error(_)) {
+            self.push_problem(entry);
+            return None;
+        }
+
+        self.output = Output::None;
+
+        match entry.message() {
+            Message::TransactionStarted => {
+                let transaction = self.make_transaction();
+                self.reset(entry);
+                return transaction;
+            }
+            Message::TransactionCompleted | Message::TransactionInterrupted
+                if !self.is_within_transaction() =>
+            {
+                self.keep_orphan(entry);
+            }
+            Message::TransactionCompleted => {
+                self.completion.replace(entry);
+
+                if self.eager {
+                    return self.emit();
+                }
+            }
+            Message::TransactionInterrupted => {
+                self.interruption.replace(entry);
+
+                if self.eager {
+                    return self.emit();
+                }
+            }
+            Message::Pacnew(_) | Message::Pacsave(_) => {
+                if self.is_within_transaction() {
+                    match entry.into_message() {
+                        Message::Pacnew(config_file) => self.pacnew.push(config_file),
+                        Message::Pacsave(config_file) => self.pacsave.push(config_file),
+                        _ => {}
+                    }
+                } else {
+                    self.keep_orphan(entry);
+                }
+            }
+            Message::Warning(_) | Message::Error(_) | Message::Other(_) => {}
+            Message::Running(_) => {
+                self.discard_pending();
+                self.pending_command.replace(entry);
+            }
+            Message::SynchronizingPackageLists => {
+                self.outstanding_sync.replace(entry.clone());
+
+                if let Some(sync) = self.pending_sync.replace(entry) {
+                    self.keep_orphan(sync);
+                }
+            }
+            Message::StartingFullSystemUpgrade => {
+                self.outstanding_sync = None;
+
+                if let Some(full_system_upgrade) = self.pending_full_system_upgrade.replace(entry) {
+                    self.keep_orphan(full_system_upgrade);
+                }
+            }
+            Message::Installed(_)
+            | Message::Upgraded(_)
+            | Message::Downgraded(_)
+            | Message::Reinstalled(_)
+            | Message::Removed(_) => {
+                if self.is_within_transaction() {
+                    self.record(entry.into_message());
+                } else if self.keep_orphans {
+                    self.orphans.push(entry);
+                } else {
+                    discard(entry.message());
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Return the last transaction, if any.
+    ///
+    /// Entries recorded for a next transaction that has not been started are kept as orphans.
+    pub fn finish(&mut self) -> Option<Transaction> {
+        let transaction = self.make_transaction();
+        self.discard_pending();
+        transaction
+    }
+
+    /// Record hooks and their scriptlet output.
+    ///
+    /// Hooks logged after the command, database synchronization or full system upgrade announcement
+    /// of the next transaction or before any transaction are pre-transaction hooks of the next transaction.
+    /// Otherwise they are post-transaction hooks of the current one.
+    /// Logs without `Running` entries, as written by old versions of pacman, lack these announcements,
+    /// so pre-transaction hooks are attributed to the preceding transaction as post-transaction hooks.
+    ///
+    /// Other lines are kept as such.
+    fn push_other(&mut self, entry: Entry) {
+        match entry.issuer() {
+            Issuer::Alpm if Hook::is_announced_by(&entry) => {
+                self.output = if self.is_next() {
+                    self.pending_hooks
+                        .extend(Hook::new(entry, HookPhase::PreTransaction));
+                    Output::Pending
+                } else if self.is_current() {
+                    self.hooks
+                        .extend(Hook::new(entry, HookPhase::PostTransaction));
+                    Output::Current
+                } else {
+                    self.keep_orphan(entry);
+                    Output::None
+                };
+            }
+            Issuer::AlpmScriptlet => match self.output {
+                Output::None => self.push_line(entry),
+                Output::Pending => push_output(self.pending_hooks.last_mut(), entry),
+                Output::Current => push_output(self.hooks.last_mut(), entry),
+                Output::Installed => push_output(self.installed.last_mut(), entry),
+                Output::Upgraded => push_output(self.upgraded.last_mut(), entry),
+                Output::Downgraded => push_output(self.downgraded.last_mut(), entry),
+                Output::Reinstalled => push_output(self.reinstalled.last_mut(), entry),
+                Output::Removed => push_output(self.removed.last_mut(), entry),
+            },
+            _ => self.push_line(entry),
+        }
+    }
+
+    /// Record a line that is neither a hook nor scriptlet output.
+    fn push_line(&mut self, entry: Entry) {
+        self.output = Output::None;
+
+        if self.is_current() {
+            self.other.push(entry);
+        } else {
+            self.push_unattached(entry);
+        }
+    }
+
+    /// Record warnings and errors of the current or next transaction.
+    ///
+    /// Warnings and errors of scriptlets are also kept as part of their output.
+    fn push_problem(&mut self, entry: Entry) {
+        if self.is_current() {
+            self.problems.push(entry.clone());
+        } else {
+            self.push_unattached(entry.clone());
+        }
+
+        if matches!(entry.issuer(), Issuer::AlpmScriptlet) && self.output != Output::None {
+            self.push_other(entry);
+        } else {
+            self.output = Output::None;
+        }
+    }
+
+    /// Record an entry for the next transaction or keep it as an orphan.
+    fn push_unattached(&mut self, entry: Entry) {
+        if self.is_next() {
+            self.pending_entries.push(entry);
+        } else {
+            self.keep_orphan(entry);
+        }
+    }
+
+    /// Keep the announcements, hooks and entries recorded for a next transaction as orphans,
+    /// since none has been started.
+    ///
+    /// Warnings and errors in the output of hooks are already among the recorded entries.
+    fn discard_pending(&mut self) {
+        let mut entries: Vec<_> = self
+            .pending_command
+            .take()
+            .into_iter()
+            .chain(self.pending_sync.take())
+            .chain(self.pending_full_system_upgrade.take())
+            .chain(
+                take(&mut self.pending_hooks)
+                    .into_iter()
+                    .flat_map(Hook::into_entries),
+            )
+            .filter(|entry| !matches!(entry.message(), Message::Warning(_) | Message::Error(_)))
+            .chain(take(&mut self.pending_entries))
+            .collect();
+        entries.sort_by_key(Entry::timestamp);
+
+        for entry in entries {
+            self.keep_orphan(entry);
+        }
+    }
+
+    fn reset(&mut self, start: Entry) {
+        self.command = self.pending_command.take();
+        self.sync = self.pending_sync.take();
+        self.last_sync.clone_from(&self.outstanding_sync);
+        self.full_system_upgrade = self.pending_full_system_upgrade.take();
+        self.start.replace(start);
+        self.emitted = false;
+        self.completion = None;
+        self.interruption = None;
+        self.installed.clear();
+        self.upgraded.clear();
+        self.downgraded.clear();
+        self.reinstalled.clear();
+        self.removed.clear();
+        self.hooks = take(&mut self.pending_hooks);
+        self.pacnew.clear();
+        self.pacsave.clear();
+        self.problems.clear();
+        self.other.clear();
+
+        for entry in take(&mut self.pending_entries) {
+            if matches!(entry.message(), Message::Warning(_) | Message::Error(_)) {
+                self.problems.push(entry);
+            } else {
+                self.other.push(entry);
+            }
+        }
+    }
+
+    /// Return whether entries belong to the next transaction rather than the current one.
+    const fn is_next(&self) -> bool {
+        self.pending_command.is_some()
+            || self.pending_sync.is_some()
+            || self.pending_full_system_upgrade.is_some()
+            || (self.start.is_none() && !self.emitted)
+    }
+
+    /// Return whether entries belong to the current transaction rather than the next one.
+    const fn is_current(&self) -> bool {
+        self.start.is_some()
+            && self.pending_command.is_none()
+            && self.pending_sync.is_none()
+            && self.pending_full_system_upgrade.is_none()
+    }
+
+    const fn is_within_transaction(&self) -> bool {
+        self.start.is_some() && self.completion.is_none() && self.interruption.is_none()
+    }
+
+    fn record(&mut self, message: Message) {
+        self.output = match message {
+            Message::Installed(package) => {
+                self.installed.push(PackageEvent::new(package));
+                Output::Installed
+            }
+            Message::Upgraded(upgrade) => {
+                self.upgraded.push(PackageEvent::new(upgrade));
+                Output::Upgraded
+            }
+            Message::Downgraded(downgrade) => {
+                self.downgraded.push(PackageEvent::new(downgrade));
+                Output::Downgraded
+            }
+            Message::Reinstalled(package) => {
+                self.reinstalled.push(PackageEvent::new(package));
+                Output::Reinstalled
+            }
+            Message::Removed(package) => {
+                self.removed.push(PackageEvent::new(package));
+                Output::Removed
+            }
+            _ => Output::None,
+        };
+    }
+
+    /// Emit the current transaction before the next one has been started.
+    fn emit(&mut self) -> Option<Transaction> {
+        self.emitted = true;
+        self.make_transaction()
+    }
+
+    fn make_transaction(&mut self) -> Option<Transaction> {
+        self.start.take().map(|start| Transaction {
+            command: self.command.take(),
+            sync: self.sync.take(),
+            last_sync: self.last_sync.take(),
+            full_system_upgrade: self.full_system_upgrade.take(),
+            start,
+            installed: take(&mut self.installed).into_boxed_slice(),
+            upgraded: take(&mut self.upgraded).into_boxed_slice(),
+            downgraded: take(&mut self.downgraded).into_boxed_slice(),
+            reinstalled: take(&mut self.reinstalled).into_boxed_slice(),
+            removed: take(&mut self.removed).into_boxed_slice(),
+            completion: self.completion.take(),
+            interruption: self.interruption.take(),
+            hooks: take(&mut self.hooks).into_boxed_slice(),
+            pacnew: take(&mut self.pacnew).into_boxed_slice(),
+            pacsave: take(&mut self.pacsave).into_boxed_slice(),
+            problems: take(&mut self.problems).into_boxed_slice(),
+            other: take(&mut self.other).into_boxed_slice(),
+        })
+    }
+}
+
+/// Warn about a package event outside of a transaction.
+fn discard(message: &Message) {
+    match message {
+        Message::Installed(package) => {
+            warn!("discarding package install outside of transaction: {package:?}");
+        }
+        Message::Upgraded(upgrade) => {
+            warn!("discarding package upgrade outside of transaction: {upgrade:?}");
+        }
+        Message::Downgraded(downgrade) => {
+            warn!("discarding package downgrade outside of transaction: {downgrade:?}");
+        }
+        Message::Reinstalled(package) => {
+            warn!("discarding package reinstall outside of transaction: {package:?}");
+        }
+        Message::Removed(package) => {
+            warn!("discarding package removal outside of transaction: {package:?}");
+        }
+        _ => {}
+    }
+}
+
+/// Targets of scriptlet output.
+trait Attach {
+    fn attach(&mut self, entry: Entry);
+}
+
+impl Attach for Hook {
+    fn attach(&mut self, entry: Entry) {
+        self.push_output(entry);
+    }
+}
+
+impl<T> Attach for PackageEvent<T> {
+    fn attach(&mut self, entry: Entry) {
+        self.push_output(entry);
+    }
+}
+
+/// Attach scriptlet output to the given target, if any.
+fn push_output(target: Option<&mut impl Attach>, entry: Entry) {
+    if let Some(target) = target {
+        target.attach(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::Grouper;
+    use crate::{Entry, HookPhase, IntoEntry, LogReader, Message, Outcome, Transaction};
+
+    fn group(log: &str) -> Vec<Transaction> {
+        let mut grouper = Grouper::new(false);
+        let mut transactions: Vec<_> = LogReader::new(log.as_bytes())
+            .map(Result::unwrap)
+            .filter(IntoEntry::is_groupable)
+            .filter_map(|entry| grouper.push(entry))
+            .collect();
+        transactions.extend(grouper.finish());
+        transactions
+    }
+
+    const HOOKS: &str = "\
+[2024-03-04T10:00:00+0100] [PACMAN] Running 'pacman -S linux'
+[2024-03-04T10:00:01+0100] [ALPM] running '60-mkinitcpio-remove.hook'...
+[2024-03-04T10:00:01+0100] [ALPM] transaction started
+[2024-03-04T10:00:02+0100] [ALPM] upgraded linux (6.7.8.arch1-1 -> 6.7.9.arch1-1)
+[2024-03-04T10:00:02+0100] [ALPM] some unrecognized note
+[2024-03-04T10:00:03+0100] [ALPM] transaction completed
+[2024-03-04T10:00:03+0100] [ALPM] running '30-systemd-update.hook'...
+[2024-03-04T10:00:03+0100] [ALPM] running '90-mkinitcpio-install.hook'...
+[2024-03-04T10:00:04+0100] [ALPM-SCRIPTLET] ==> Building image from preset: /etc/mkinitcpio.d/linux.preset: 'default'
+[2024-03-04T10:01:00+0100] [PACMAN] Running 'pacman -S vim'
+[2024-03-04T10:01:01+0100] [ALPM] running '00-pre.hook'...
+[2024-03-04T10:01:01+0100] [ALPM] transaction started
+[2024-03-04T10:01:02+0100] [ALPM] installed vim (9.1.0142-1)
+[2024-03-04T10:01:03+0100] [ALPM] transaction completed
+";
+
+    #[test]
+    fn assigns_hooks_to_phases() {
+        let transactions = group(HOOKS);
+        assert_eq!(transactions.len(), 2);
+
+        let hooks: Vec<_> = transactions[0]
+            .hooks()
+            .iter()
+            .map(|hook| (hook.name(), hook.phase()))
+            .collect();
+        assert_eq!(
+            hooks,
+            [
+                ("60-mkinitcpio-remove.hook", HookPhase::PreTransaction),
+                ("30-systemd-update.hook", HookPhase::PostTransaction),
+                ("90-mkinitcpio-install.hook", HookPhase::PostTransaction),
+            ]
+        );
+        assert_eq!(
+            transactions[0].hooks()[2]
+                .output_lines()
+                .collect::<Vec<_>>(),
+            ["==> Building image from preset: /etc/mkinitcpio.d/linux.preset: 'default'"]
+        );
+
+        let hooks: Vec<_> = transactions[1]
+            .hooks()
+            .iter()
+            .map(|hook| (hook.name(), hook.phase()))
+            .collect();
+        assert_eq!(hooks, [("00-pre.hook", HookPhase::PreTransaction)]);
+    }
+
+    #[test]
+    fn keeps_other_lines() {
+        let transactions = group(HOOKS);
+        assert_eq!(
+            transactions[0]
+                .other()
+                .iter()
+                .map(Entry::message)
+                .collect::<Vec<_>>(),
+            [&Message::Other("some unrecognized note".to_string())]
+        );
+        assert!(transactions[1].other().is_empty());
+    }
+
+    #[test]
+    fn attaches_scriptlet_output_to_package_events() {
+        let transactions = group(
+            "\
+[2024-03-05T09:00:00+0100] [PACMAN] Running 'pacman -Syu'
+[2024-03-05T09:00:01+0100] [PACMAN] synchronizing package lists
+[2024-03-05T09:00:02+0100] [PACMAN] starting full system upgrade
+[2024-03-05T09:00:03+0100] [ALPM] transaction started
+[2024-03-05T09:00:04+0100] [ALPM] installed postgresql (16.2-1)
+[2024-03-05T09:00:04+0100] [ALPM-SCRIPTLET] Initialize the database cluster with initdb.
+[2024-03-05T09:00:05+0100] [ALPM] upgraded glibc (2.39-1 -> 2.39-2)
+[2024-03-05T09:00:05+0100] [ALPM-SCRIPTLET] Generating locales...
+[2024-03-05T09:00:06+0100] [ALPM-SCRIPTLET]   en_US.UTF-8... done
+[2024-03-05T09:00:06+0100] [ALPM] upgraded bash (5.2.026-1 -> 5.2.026-2)
+[2024-03-05T09:00:07+0100] [ALPM] transaction completed
+",
+        );
+        assert_eq!(transactions.len(), 1);
+        let transaction = &transactions[0];
+
+        let installed = &transaction.installed()[0];
+        assert_eq!(installed.name(), "postgresql");
+        assert_eq!(
+            installed.scriptlet_lines().collect::<Vec<_>>(),
+            ["Initialize the database cluster with initdb."]
+        );
+
+        let upgraded: Vec<_> = transaction
+            .upgraded()
+            .iter()
+            .map(|event| (event.name(), event.scriptlet_lines().collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(
+            upgraded,
+            [
+                (
+                    "glibc",
+                    vec!["Generating locales...", "  en_US.UTF-8... done"]
+                ),
+                ("bash", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_interrupted_and_restarted_transactions() {
+        let transactions = group(
+            "\
+[2024-03-06T20:00:00+0100] [PACMAN] Running 'pacman -S firefox'
+[2024-03-06T20:00:01+0100] [ALPM] transaction started
+[2024-03-06T20:00:02+0100] [ALPM] installed nss (3.98-1)
+[2024-03-06T20:00:03+0100] [ALPM] transaction interrupted
+[2024-03-06T20:01:00+0100] [PACMAN] Running 'pacman -S firefox'
+[2024-03-06T20:01:01+0100] [ALPM] transaction started
+[2024-03-06T20:01:02+0100] [ALPM] installed firefox (123.0.1-1)
+[2024-03-06T20:01:03+0100] [ALPM] transaction completed
+",
+        );
+        assert_eq!(transactions.len(), 2);
+
+        let interrupted = &transactions[0];
+        assert_eq!(interrupted.outcome(), Outcome::Interrupted);
+        assert!(interrupted.interruption().is_some());
+        assert!(interrupted.completion().is_none());
+        assert_eq!(interrupted.packages().collect::<Vec<_>>(), ["nss"]);
+
+        let restarted = &transactions[1];
+        assert_eq!(restarted.outcome(), Outcome::Completed);
+        assert!(restarted.interruption().is_none());
+        assert_eq!(restarted.packages().collect::<Vec<_>>(), ["firefox"]);
+    }
+
+    #[test]
+    fn keeps_hooks_of_aborted_transactions_as_orphans() {
+        let mut grouper = Grouper::new(false).keep_orphans();
+        let log = "\
+[2024-03-07T08:00:00+0100] [PACMAN] Running 'pacman -S linux'
+[2024-03-07T08:00:01+0100] [ALPM] running '10-abort.hook'...
+[2024-03-07T08:00:01+0100] [ALPM-SCRIPTLET] refusing to upgrade the running kernel
+[2024-03-07T08:00:02+0100] [ALPM] error: command failed to execute correctly
+[2024-03-07T08:01:00+0100] [PACMAN] Running 'pacman -S vim'
+[2024-03-07T08:01:01+0100] [ALPM] transaction started
+[2024-03-07T08:01:02+0100] [ALPM] installed vim (9.1.0142-1)
+[2024-03-07T08:01:03+0100] [ALPM] transaction completed
+";
+        let mut transactions: Vec<_> = LogReader::new(log.as_bytes())
+            .map(Result::unwrap)
+            .filter_map(|entry| grouper.push(entry))
+            .collect();
+        transactions.extend(grouper.finish());
+
+        assert_eq!(transactions.len(), 1);
+        assert!(transactions[0].hooks().is_empty());
+
+        let orphans = grouper.take_orphans();
+        assert!(matches!(orphans[0].message(), Message::Running(_)));
+        assert_eq!(
+            orphans[1..].iter().map(Entry::message).collect::<Vec<_>>(),
+            [
+                &Message::Other("running '10-abort.hook'...".to_string()),
+                &Message::Other("refusing to upgrade the running kernel".to_string()),
+                &Message::Error("command failed to execute correctly".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_config_files_outside_of_transactions_as_orphans() {
+        let mut grouper = Grouper::new(false).keep_orphans();
+        let log = "\
+[2024-03-08T11:00:00+0100] [ALPM] transaction started
+[2024-03-08T11:00:01+0100] [ALPM] upgraded pacman (6.0.2-9 -> 6.1.0-1)
+[2024-03-08T11:00:01+0100] [ALPM] warning: /etc/pacman.conf installed as /etc/pacman.conf.pacnew
+[2024-03-08T11:00:02+0100] [ALPM] transaction completed
+[2024-03-08T11:00:03+0100] [ALPM] warning: /etc/makepkg.conf installed as /etc/makepkg.conf.pacnew
+";
+        let mut transactions: Vec<_> = LogReader::new(log.as_bytes())
+            .map(Result::unwrap)
+            .filter_map(|entry| grouper.push(entry))
+            .collect();
+        transactions.extend(grouper.finish());
+
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].pacnew_files().len(), 1);
+        assert!(matches!(
+            grouper.take_orphans()[..],
+            [ref entry] if matches!(entry.message(), Message::Pacnew(_))
+        ));
+    }
+
+    #[test]
+    fn tracks_synchronizations_across_invocations() {
+        let transactions = group(
+            "\
+[2024-03-10T08:00:00+0100] [PACMAN] Running 'pacman -Sy'
+[2024-03-10T08:00:01+0100] [PACMAN] synchronizing package lists
+[2024-03-10T09:00:00+0100] [PACMAN] Running 'pacman -S vim'
+[2024-03-10T09:00:01+0100] [ALPM] transaction started
+[2024-03-10T09:00:02+0100] [ALPM] installed vim (9.1.0142-1)
+[2024-03-10T09:00:03+0100] [ALPM] transaction completed
+[2024-03-10T10:00:00+0100] [PACMAN] Running 'pacman -Syu'
+[2024-03-10T10:00:01+0100] [PACMAN] synchronizing package lists
+[2024-03-10T10:00:02+0100] [PACMAN] starting full system upgrade
+[2024-03-10T10:00:03+0100] [ALPM] transaction started
+[2024-03-10T10:00:04+0100] [ALPM] upgraded vim (9.1.0142-1 -> 9.1.0150-1)
+[2024-03-10T10:00:05+0100] [ALPM] transaction completed
+[2024-03-10T11:00:00+0100] [PACMAN] Running 'pacman -S git'
+[2024-03-10T11:00:01+0100] [ALPM] transaction started
+[2024-03-10T11:00:02+0100] [ALPM] installed git (2.44.0-1)
+[2024-03-10T11:00:03+0100] [ALPM] transaction completed
+",
+        );
+        assert_eq!(transactions.len(), 3);
+
+        let partial = &transactions[0];
+        assert!(!partial.is_synchronized());
+        assert_eq!(
+            partial.last_sync().map(Entry::timestamp),
+            Some(DateTime::parse_from_rfc3339("2024-03-10T08:00:01+01:00").unwrap())
+        );
+        assert!(partial.is_sync_without_upgrade());
+
+        let upgrade = &transactions[1];
+        assert!(upgrade.is_synchronized());
+        assert!(upgrade.is_full_system_upgrade());
+        assert!(upgrade.last_sync().is_none());
+        assert!(!upgrade.is_sync_without_upgrade());
+        assert!(!upgrade.is_partial_upgrade());
+
+        assert!(transactions[2].last_sync().is_none());
+        assert!(!transactions[2].is_sync_without_upgrade());
+    }
+}
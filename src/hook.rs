@@ -0,0 +1,94 @@
+use std::iter::once;
+
+pub use phase::HookPhase;
+
+use crate::{Entry, Message};
+
+mod phase;
+
+/// A hook that ran as part of a transaction, along with its output.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Hook {
+    name: String,
+    phase: HookPhase,
+    entry: Entry,
+    output: Vec<Entry>,
+}
+
+impl Hook {
+    /// Create a new hook from a `running '<name>'...` entry.
+    ///
+    /// Returns `None` if the entry does not announce a hook.
+    pub(crate) fn new(entry: Entry, phase: HookPhase) -> Option<Self> {
+        let name = name(&entry)?.to_string();
+
+        Some(Self {
+            name,
+            phase,
+            entry,
+            output: Vec::new(),
+        })
+    }
+
+    /// Return whether the entry announces a hook.
+    pub(crate) fn is_announced_by(entry: &Entry) -> bool {
+        name(entry).is_some()
+    }
+
+    /// Return the file name of the hook, e.g. `90-mkinitcpio-install.hook`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the phase in which the hook ran.
+    #[must_use]
+    pub const fn phase(&self) -> HookPhase {
+        self.phase
+    }
+
+    /// Return the entry that announced the hook.
+    #[must_use]
+    pub const fn entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    /// Return the scriptlet output entries logged by the hook.
+    #[must_use]
+    pub fn output(&self) -> &[Entry] {
+        &self.output
+    }
+
+    /// Return the lines of text logged by the hook.
+    pub fn output_lines(&self) -> impl Iterator<Item = &str> {
+        self.output
+            .iter()
+            .filter_map(|entry| match entry.message() {
+                Message::Warning(text) | Message::Error(text) | Message::Other(text) => {
+                    Some(text.as_str())
+                }
+                _ => None,
+            })
+            .flat_map(str::lines)
+    }
+
+    /// Return the entry that announced the hook followed by its output entries.
+    pub(crate) fn into_entries(self) -> impl Iterator<Item = Entry> {
+        once(self.entry).chain(self.output)
+    }
+
+    /// Attach an output entry to the hook.
+    pub(crate) fn push_output(&mut self, entry: Entry) {
+        self.output.push(entry);
+    }
+}
+
+/// Return the name of the hook announced by the entry, if any.
+fn name(entry: &Entry) -> Option<&str> {
+    let Message::Other(text) = entry.message() else {
+        return None;
+    };
+
+    text.strip_prefix("running '")
+        .and_then(|text| text.strip_suffix("'..."))
+}
@@ -0,0 +1,8 @@
+/// Phase of a transaction in which a hook ran.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum HookPhase {
+    /// The hook ran before the transaction was started.
+    PreTransaction,
+    /// The hook ran after the transaction was started, usually after its completion.
+    PostTransaction,
+}
@@ -0,0 +1,522 @@
+use std::collections::HashMap;
+use std::fs::{metadata, File, Metadata};
+use std::io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+pub use span::TransactionSpan;
+
+use crate::grouper::Grouper;
+use crate::reader::trim_newline;
+use crate::{Entry, IntoEntry, LogReader, Message, Transaction};
+
+mod span;
+
+const HEADER: &str = "pacmanlog-index 2";
+const EXTENSION: &str = "idx";
+
+/// A persistent index of the transactions and package events of a logfile.
+///
+/// The index records byte offsets, so that queries can seek straight to the relevant entries.
+/// It is invalidated when the size, modification time or inode of the logfile change.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Index {
+    path: PathBuf,
+    fingerprint: Fingerprint,
+    transactions: Vec<TransactionSpan>,
+    packages: HashMap<String, Vec<u64>>,
+}
+
+impl Index {
+    /// Load the sidecar index of the given logfile, rebuilding and saving it if it is missing or stale.
+    ///
+    /// The sidecar index is stored next to the logfile with an additional `.idx` extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the logfile could not be read or the index could not be saved.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        Self::open_with(path, Self::sidecar_path(path))
+    }
+
+    /// Load the index at `index_path` for the given logfile, rebuilding and saving it if it is missing or stale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the logfile could not be read or the index could not be saved.
+    pub fn open_with(
+        path: impl AsRef<Path>,
+        index_path: impl AsRef<Path>,
+    ) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let index_path = index_path.as_ref();
+
+        if let Ok(index) = Self::load(path, index_path) {
+            if index.is_valid()? {
+                return Ok(index);
+            }
+        }
+
+        let index = Self::build(path)?;
+        index.save(index_path)?;
+        Ok(index)
+    }
+
+    /// Return the default path of the sidecar index of the given logfile.
+    #[must_use]
+    pub fn sidecar_path(path: &Path) -> PathBuf {
+        let mut index_path = path.as_os_str().to_os_string();
+        index_path.push(".");
+        index_path.push(EXTENSION);
+        index_path.into()
+    }
+
+    /// Build the index of the given logfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the logfile could not be read.
+    pub fn build(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+        let fingerprint = Fingerprint::try_from(&file.metadata()?)?;
+        let mut reader = LogReader::new(BufReader::new(file));
+        let mut transactions: Vec<TransactionSpan> = Vec::new();
+        let mut packages: HashMap<String, Vec<u64>> = HashMap::new();
+        let mut pending_command = None;
+        let mut pending_sync = None;
+        let mut pending_full_system_upgrade = None;
+        let mut is_open = false;
+
+        while let Some(entry) = reader.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let offset = reader.entry_offset();
+
+            match entry.message() {
+                Message::Running(_) => {
+                    if let Some(previous) = transactions.last_mut().filter(|_| is_open) {
+                        previous.set_end(offset);
+                        is_open = false;
+                    }
+
+                    pending_command = Some(offset);
+                    pending_sync = None;
+                    pending_full_system_upgrade = None;
+                }
+                Message::SynchronizingPackageLists => pending_sync = Some(offset),
+                Message::StartingFullSystemUpgrade => pending_full_system_upgrade = Some(offset),
+                Message::TransactionStarted => {
+                    let context = pending_command
+                        .take()
+                        .into_iter()
+                        .chain(pending_sync.take())
+                        .chain(pending_full_system_upgrade.take())
+                        .fold(offset, u64::min);
+
+                    if let Some(previous) = transactions.last_mut().filter(|_| is_open) {
+                        previous.set_end(context);
+                    }
+
+                    transactions.push(TransactionSpan::new(context, offset, fingerprint.size));
+                    is_open = true;
+                }
+                Message::Installed(package)
+                | Message::Reinstalled(package)
+                | Message::Removed(package) => {
+                    packages
+                        .entry(package.name().to_string())
+                        .or_default()
+                        .push(offset);
+                }
+                Message::Upgraded(upgrade) => {
+                    packages
+                        .entry(upgrade.name().to_string())
+                        .or_default()
+                        .push(offset);
+                }
+                Message::Downgraded(downgrade) => {
+                    packages
+                        .entry(downgrade.name().to_string())
+                        .or_default()
+                        .push(offset);
+                }
+                Message::TransactionCompleted
+                | Message::TransactionInterrupted
+                | Message::Pacnew(_)
+                | Message::Pacsave(_)
+                | Message::Warning(_)
+                | Message::Error(_)
+                | Message::Other(_) => {}
+            }
+        }
+
+        Ok(Self {
+            path,
+            fingerprint,
+            transactions,
+            packages,
+        })
+    }
+
+    /// Load the index at `index_path` for the given logfile without validating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the index could not be read or is malformed.
+    pub fn load(path: impl AsRef<Path>, index_path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut lines = BufReader::new(File::open(index_path)?).lines();
+
+        if lines.next().transpose()?.as_deref() != Some(HEADER) {
+            return Err(invalid_data("missing index header"));
+        }
+
+        let fingerprint = lines
+            .next()
+            .transpose()?
+            .ok_or_else(|| invalid_data("missing fingerprint"))?
+            .parse()?;
+        let mut transactions = Vec::new();
+        let mut packages = HashMap::new();
+
+        for line in lines {
+            let line = line?;
+            let mut fields = line.split(' ');
+
+            match fields.next() {
+                Some("transaction") => {
+                    let [context, start, end] = parse_offsets(fields)?;
+                    transactions.push(TransactionSpan::new(context, start, end));
+                }
+                Some("package") => {
+                    let name = fields
+                        .next()
+                        .ok_or_else(|| invalid_data("missing package name"))?;
+                    let offsets = fields
+                        .map(|offset| offset.parse().map_err(|_| invalid_data(offset)))
+                        .collect::<Result<_, _>>()?;
+                    packages.insert(name.to_string(), offsets);
+                }
+                _ => return Err(invalid_data(&line)),
+            }
+        }
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            fingerprint,
+            transactions,
+            packages,
+        })
+    }
+
+    /// Save the index to the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the index could not be written.
+    pub fn save(&self, index_path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(index_path)?);
+        writeln!(writer, "{HEADER}")?;
+        writeln!(writer, "{}", self.fingerprint)?;
+
+        for span in &self.transactions {
+            writeln!(
+                writer,
+                "transaction {} {} {}",
+                span.context(),
+                span.start(),
+                span.end()
+            )?;
+        }
+
+        for (name, offsets) in &self.packages {
+            write!(writer, "package {name}")?;
+
+            for offset in offsets {
+                write!(writer, " {offset}")?;
+            }
+
+            writeln!(writer)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Return whether the index still matches its logfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the metadata of the logfile could not be read.
+    pub fn is_valid(&self) -> std::io::Result<bool> {
+        Ok(Fingerprint::try_from(&metadata(&self.path)?)? == self.fingerprint)
+    }
+
+    /// Return the amount of indexed transactions.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Return whether no transactions have been indexed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Return the byte ranges of the indexed transactions.
+    #[must_use]
+    pub fn spans(&self) -> &[TransactionSpan] {
+        &self.transactions
+    }
+
+    /// Return the transaction with the given zero-based number.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the logfile could not be read.
+    pub fn transaction(&self, number: usize) -> std::io::Result<Option<Transaction>> {
+        let Some(span) = self.transactions.get(number) else {
+            return Ok(None);
+        };
+
+        let mut grouper = Grouper::default();
+
+        for entry in span
+            .entries(&self.path)?
+            .into_iter()
+            .filter(IntoEntry::is_groupable)
+        {
+            grouper.push(entry);
+        }
+
+        Ok(grouper.finish())
+    }
+
+    /// Return all entries that installed, upgraded, downgraded, reinstalled or removed the given package.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the logfile could not be read.
+    pub fn package_history(&self, name: &str) -> std::io::Result<Vec<Entry>> {
+        let Some(offsets) = self.packages.get(name) else {
+            return Ok(Vec::new());
+        };
+
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut buffer = Vec::new();
+        let mut entries = Vec::with_capacity(offsets.len());
+
+        for &offset in offsets {
+            reader.seek(SeekFrom::Start(offset))?;
+            buffer.clear();
+            reader.read_until(b'\n', &mut buffer)?;
+            let line = String::from_utf8_lossy(trim_newline(&buffer));
+            entries.push(
+                line.parse()
+                    .map_err(|error| Error::new(ErrorKind::InvalidData, error))?,
+            );
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Identifies a version of a logfile.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Fingerprint {
+    size: u64,
+    seconds: u64,
+    nanoseconds: u32,
+    inode: u64,
+}
+
+impl TryFrom<&Metadata> for Fingerprint {
+    type Error = Error;
+
+    fn try_from(metadata: &Metadata) -> Result<Self, Self::Error> {
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+        Ok(Self {
+            size: metadata.len(),
+            seconds: modified.as_secs(),
+            nanoseconds: modified.subsec_nanos(),
+            inode: inode(metadata),
+        })
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "file {} {} {} {}",
+            self.size, self.seconds, self.nanoseconds, self.inode
+        )
+    }
+}
+
+impl std::str::FromStr for Fingerprint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(' ');
+
+        if fields.next() != Some("file") {
+            return Err(invalid_data(s));
+        }
+
+        let [size, seconds, nanoseconds, inode] = parse_offsets(fields)?;
+        Ok(Self {
+            size,
+            seconds,
+            nanoseconds: u32::try_from(nanoseconds).map_err(|_| invalid_data(s))?,
+            inode,
+        })
+    }
+}
+
+/// Return the inode of a file, if the platform has any.
+#[cfg(unix)]
+pub fn inode(metadata: &Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::ino(metadata)
+}
+
+/// Return the inode of a file, if the platform has any.
+#[cfg(not(unix))]
+pub const fn inode(_: &Metadata) -> u64 {
+    0
+}
+
+fn parse_offsets<'a, const N: usize>(
+    mut fields: impl Iterator<Item = &'a str>,
+) -> std::io::Result<[u64; N]> {
+    let mut offsets = [0; N];
+
+    for offset in &mut offsets {
+        let field = fields.next().ok_or_else(|| invalid_data("missing field"))?;
+        *offset = field.parse().map_err(|_| invalid_data(field))?;
+    }
+
+    if fields.next().is_some() {
+        return Err(invalid_data("trailing fields"));
+    }
+
+    Ok(offsets)
+}
+
+fn invalid_data(text: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("malformed index: {text}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    use super::Index;
+    use crate::fixture::{TempDir, LOG};
+    use crate::{LogReader, Message, Transactions};
+
+    #[test]
+    fn yields_the_same_transactions_as_grouping() {
+        let dir = TempDir::new("index-transactions");
+        let index = Index::build(dir.write("pacman.log", LOG)).unwrap();
+        let transactions = || {
+            LogReader::new(LOG.as_bytes())
+                .map(Result::unwrap)
+                .transactions()
+        };
+        assert_eq!(index.len(), transactions().count());
+
+        for number in 0..index.len() {
+            assert_eq!(
+                index.transaction(number).unwrap(),
+                transactions().nth(number)
+            );
+        }
+    }
+
+    #[test]
+    fn ends_spans_at_the_next_invocation() {
+        let dir = TempDir::new("index-spans");
+        let index = Index::build(dir.write("pacman.log", LOG)).unwrap();
+        let offset = |line: &str| u64::try_from(LOG.find(line).unwrap()).unwrap();
+
+        let span = index.spans()[0];
+        assert_eq!(span.context(), offset("[2024-03-01T09:00:00+0100]"));
+        assert_eq!(span.start(), offset("[2024-03-01T09:00:04+0100]"));
+        assert_eq!(span.end(), offset("[2024-03-02T10:00:00+0100]"));
+
+        let span = index.spans()[1];
+        assert_eq!(span.context(), offset("[2024-03-02T10:05:00+0100]"));
+        assert_eq!(span.end(), offset("[2024-03-02T10:06:00+0100]"));
+
+        let span = index.spans()[index.len() - 1];
+        assert_eq!(span.end(), u64::try_from(LOG.len()).unwrap());
+    }
+
+    #[test]
+    fn loads_saved_index() {
+        let dir = TempDir::new("index-load");
+        let path = dir.write("pacman.log", LOG);
+        let index = Index::build(&path).unwrap();
+        let index_path = Index::sidecar_path(&path);
+        index.save(&index_path).unwrap();
+
+        let loaded = Index::load(&path, &index_path).unwrap();
+        assert_eq!(loaded, index);
+        assert!(loaded.is_valid().unwrap());
+        assert_eq!(Index::open(&path).unwrap(), index);
+
+        dir.write("pacman.log.idx", "pacmanlog-index 1\n");
+        assert!(Index::load(&path, &index_path).is_err());
+    }
+
+    #[test]
+    fn rebuilds_stale_index() {
+        let dir = TempDir::new("index-stale");
+        let path = dir.write("pacman.log", LOG);
+        let index = Index::open(&path).unwrap();
+
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(
+                b"[2024-03-05T13:00:00+0100] [PACMAN] Running 'pacman -S git'\n\
+[2024-03-05T13:00:01+0100] [ALPM] transaction started\n\
+[2024-03-05T13:00:02+0100] [ALPM] installed git (2.44.0-1)\n\
+[2024-03-05T13:00:03+0100] [ALPM] transaction completed\n",
+            )
+            .unwrap();
+        assert!(!index.is_valid().unwrap());
+
+        let index = Index::open(&path).unwrap();
+        assert!(index.is_valid().unwrap());
+        assert_eq!(index.len(), 6);
+        assert_eq!(
+            index
+                .transaction(5)
+                .unwrap()
+                .unwrap()
+                .packages()
+                .collect::<Vec<_>>(),
+            ["git"]
+        );
+    }
+
+    #[test]
+    fn returns_package_history() {
+        let dir = TempDir::new("index-packages");
+        let index = Index::build(dir.write("pacman.log", LOG)).unwrap();
+        let history = index.package_history("nss").unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(matches!(history[0].message(), Message::Installed(_)));
+        assert!(matches!(history[1].message(), Message::Reinstalled(_)));
+        assert!(index.package_history("emacs").unwrap().is_empty());
+    }
+}
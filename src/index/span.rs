@@ -0,0 +1,63 @@
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::{Entry, LogReader};
+
+/// Byte offsets of a transaction within a logfile.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TransactionSpan {
+    context: u64,
+    start: u64,
+    end: u64,
+}
+
+impl TransactionSpan {
+    /// Create a new transaction span.
+    pub(crate) const fn new(context: u64, start: u64, end: u64) -> Self {
+        Self {
+            context,
+            start,
+            end,
+        }
+    }
+
+    /// Return the offset of the first entry relevant to the transaction.
+    ///
+    /// This is the invoking command or the full system upgrade announcement, if any,
+    /// and the start of the transaction otherwise.
+    #[must_use]
+    pub const fn context(&self) -> u64 {
+        self.context
+    }
+
+    /// Return the offset of the `transaction started` entry.
+    #[must_use]
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Return the offset after the last entry of the transaction.
+    ///
+    /// This is the offset of the next invocation of pacman or, lacking one, the context of the next transaction.
+    /// The last transaction extends to the end of the logfile.
+    #[must_use]
+    pub const fn end(&self) -> u64 {
+        self.end
+    }
+
+    pub(crate) const fn set_end(&mut self, end: u64) {
+        self.end = end;
+    }
+
+    /// Read the entries of the transaction, including its context, from the given logfile.
+    pub(crate) fn entries(&self, path: &Path) -> std::io::Result<Vec<Entry>> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(self.context))?;
+        Ok(LogReader::new(BufReader::new(
+            file.take(self.end.saturating_sub(self.context)),
+        ))
+        .filter_map(Result::ok)
+        .collect())
+    }
+}
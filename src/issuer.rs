@@ -7,6 +7,8 @@ pub enum Issuer {
     AlpmScriptlet,
     /// Entry was issued by `pacman`.
     Pacman,
+    /// Entry did not specify an issuer, as in legacy log files.
+    Unknown,
     /// Entry was issued by something else.
     Other(String),
 }
@@ -1,16 +1,78 @@
 //! Library to parse pacman logfiles.
-pub use entry::Entry;
+#[cfg(feature = "tokio")]
+pub use async_entries::AsyncEntries;
+#[cfg(feature = "tokio")]
+pub use async_reader::AsyncLogReader;
+#[cfg(feature = "tokio")]
+pub use async_transactions::AsyncTransactions;
+pub use borrowed::{
+    ConfigFileRef, DowngradeRef, EntryRef, IssuerRef, MessageRef, PackageRef, UpgradeRef,
+};
+pub use checkpoint::Checkpoint;
+pub use entries::Entries;
+use entries_iterator::EntriesIterator;
+pub use entry::{Entry, EntryFormat};
 pub use error::Error;
+pub use follow::Follow;
+pub use grouped::Grouped;
+use grouped_iterator::GroupedIterator;
+pub use hook::{Hook, HookPhase};
+pub use index::{Index, TransactionSpan};
 pub use issuer::Issuer;
-pub use message::{Message, Package, Upgrade};
-pub use transaction::Transaction;
-pub use transactions::Transactions;
+pub use message::{Command, ConfigFile, Downgrade, Flag, Message, Operation, Package, Upgrade};
+pub use pacnew_report::PacnewReport;
+#[cfg(feature = "rayon")]
+pub use parallel::ParallelReader;
+pub use reader::{LogReader, ReadError, ReadErrorKind, ReadSummary, Window};
+pub use reverse::ReverseReader;
+use reverse_transactions_iterator::ReverseTransactionsIterator;
+pub use rotated::RotatedReader;
+pub use session::Session;
+pub use sessions::Sessions;
+use sessions_iterator::SessionsIterator;
+pub use transaction::{Outcome, PackageEvent, Transaction};
+pub use transactions::{IntoEntry, Transactions};
 use transactions_iterator::TransactionsIterator;
+#[cfg(feature = "tokio")]
+use transactions_stream::TransactionsStream;
 
+mod assembler;
+#[cfg(feature = "tokio")]
+mod async_entries;
+#[cfg(feature = "tokio")]
+mod async_reader;
+#[cfg(feature = "tokio")]
+mod async_transactions;
+mod borrowed;
+mod checkpoint;
+mod decompress;
+mod entries;
+mod entries_iterator;
 mod entry;
 mod error;
+#[cfg(test)]
+mod fixture;
+mod follow;
+mod grouped;
+mod grouped_iterator;
+mod grouper;
+mod hook;
+mod index;
 mod issuer;
 mod message;
+mod pacnew_report;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod reader;
+mod reverse;
+mod reverse_transactions_iterator;
+mod rotated;
+mod session;
+mod sessions;
+mod sessions_iterator;
+mod tokenizer;
 mod transaction;
 mod transactions;
 mod transactions_iterator;
+#[cfg(feature = "tokio")]
+mod transactions_stream;
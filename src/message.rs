@@ -1,9 +1,17 @@
 use std::str::FromStr;
 
+use crate::MessageRef;
+
+pub use command::{Command, Flag, Operation};
+pub use config_file::ConfigFile;
+pub use downgrade::Downgrade;
 pub use error::Error;
 pub use package::Package;
 pub use upgrade::Upgrade;
 
+mod command;
+mod config_file;
+mod downgrade;
 mod error;
 mod package;
 mod upgrade;
@@ -11,6 +19,10 @@ mod upgrade;
 /// Log messages.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Message {
+    /// Pacman has been invoked with a command line.
+    Running(Command),
+    /// The package databases are being synchronized.
+    SynchronizingPackageLists,
     /// A full system upgrade has been started.
     StartingFullSystemUpgrade,
     /// A transaction has been started.
@@ -19,45 +31,47 @@ pub enum Message {
     Installed(Package),
     /// A package has been upgraded.
     Upgraded(Upgrade),
+    /// A package has been downgraded.
+    Downgraded(Downgrade),
     /// A package has been reinstalled.
     Reinstalled(Package),
     /// A package has been removed.
     Removed(Package),
     /// A transaction has been completed.
     TransactionCompleted,
+    /// A transaction has been interrupted.
+    TransactionInterrupted,
+    /// A new configuration file has been installed as `.pacnew`, since the original one has been modified.
+    Pacnew(ConfigFile),
+    /// A modified configuration file has been saved as `.pacsave` while removing its package.
+    Pacsave(ConfigFile),
+    /// A warning has been issued.
+    Warning(String),
+    /// An error has been issued.
+    Error(String),
     /// Other messages.
     Other(String),
 }
 
+impl Message {
+    /// Append a continuation line to the message body.
+    ///
+    /// Returns `false` if the message is not free text.
+    pub(crate) fn append_line(&mut self, line: &str) -> bool {
+        if let Self::Warning(text) | Self::Error(text) | Self::Other(text) = self {
+            text.push('\n');
+            text.push_str(line);
+            true
+        } else {
+            false
+        }
+    }
+}
+
 impl FromStr for Message {
     type Err = Error;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut parts = text.splitn(2, ' ');
-
-        let Some(command) = parts.next() else {
-            return Ok(Self::Other(text.to_string()));
-        };
-
-        match command {
-            "installed" => Ok(Self::Installed(Package::from_str(
-                parts.next().ok_or(Error::MissingParameters)?,
-            )?)),
-            "upgraded" => Ok(Self::Upgraded(Upgrade::from_str(
-                parts.next().ok_or(Error::MissingParameters)?,
-            )?)),
-            "reinstalled" => Ok(Self::Reinstalled(Package::from_str(
-                parts.next().ok_or(Error::MissingParameters)?,
-            )?)),
-            "removed" => Ok(Self::Removed(Package::from_str(
-                parts.next().ok_or(Error::MissingParameters)?,
-            )?)),
-            _ => match text {
-                "starting full system upgrade" => Ok(Self::StartingFullSystemUpgrade),
-                "transaction started" => Ok(Self::TransactionStarted),
-                "transaction completed" => Ok(Self::TransactionCompleted),
-                text => Ok(Self::Other(text.to_string())),
-            },
-        }
+        MessageRef::try_from(text).map(|message| message.to_owned())
     }
 }
@@ -0,0 +1,136 @@
+use std::str::FromStr;
+
+pub use flag::Flag;
+pub use operation::Operation;
+
+mod flag;
+mod operation;
+mod shell;
+
+/// Long options of pacman that take a value.
+const LONG_OPTIONS_WITH_VALUE: [&str; 15] = [
+    "--arch",
+    "--assume-installed",
+    "--cachedir",
+    "--color",
+    "--config",
+    "--dbpath",
+    "--gpgdir",
+    "--hookdir",
+    "--ignore",
+    "--ignoregroup",
+    "--logfile",
+    "--overwrite",
+    "--print-format",
+    "--root",
+    "--sysroot",
+];
+
+/// Short options of pacman that take a value.
+const SHORT_OPTIONS_WITH_VALUE: [char; 2] = ['b', 'r'];
+
+/// Represents a command line that pacman has been invoked with.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Command {
+    argv: Box<[String]>,
+    operation: Option<Operation>,
+    flags: Box<[Flag]>,
+    targets: Box<[String]>,
+}
+
+impl Command {
+    /// Return the command line split into its arguments.
+    #[must_use]
+    pub const fn argv(&self) -> &[String] {
+        &self.argv
+    }
+
+    /// Return the invoked program.
+    #[must_use]
+    pub fn program(&self) -> Option<&str> {
+        self.argv.first().map(String::as_str)
+    }
+
+    /// Return the requested operation, if any.
+    #[must_use]
+    pub const fn operation(&self) -> Option<Operation> {
+        self.operation
+    }
+
+    /// Return the flags that modify the operation.
+    #[must_use]
+    pub const fn flags(&self) -> &[Flag] {
+        &self.flags
+    }
+
+    /// Return the targets of the operation, i.e. the non-option arguments.
+    #[must_use]
+    pub const fn targets(&self) -> &[String] {
+        &self.targets
+    }
+
+    /// Return whether the given flag, e.g. `--needed` or `-y`, has been passed.
+    #[must_use]
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag.name() == name)
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // Pacman joins its arguments with spaces without quoting them,
+        // so arguments containing unbalanced quotes are split on whitespace instead.
+        let argv = shell::split(text)
+            .unwrap_or_else(|| text.split_whitespace().map(str::to_string).collect());
+        let mut operation = None;
+        let mut flags = Vec::new();
+        let mut targets = Vec::new();
+        let mut arguments = argv.iter().skip(1);
+
+        while let Some(arg) = arguments.next() {
+            if arg == "--" {
+                targets.extend(arguments.by_ref().cloned());
+            } else if let Some(long) = arg.strip_prefix("--") {
+                if let Some(op) = Operation::from_long(long) {
+                    operation.get_or_insert(op);
+                } else if let Some((name, value)) = arg.split_once('=') {
+                    flags.push(Flag::new(name.to_string(), Some(value.to_string())));
+                } else if LONG_OPTIONS_WITH_VALUE.contains(&arg.as_str()) {
+                    flags.push(Flag::new(arg.clone(), arguments.next().cloned()));
+                } else {
+                    flags.push(Flag::new(arg.clone(), None));
+                }
+            } else if let Some(shorts) = arg.strip_prefix('-').filter(|rest| !rest.is_empty()) {
+                let mut shorts = shorts.chars();
+
+                while let Some(short) = shorts.next() {
+                    if let Some(op) = Operation::from_short(short) {
+                        operation.get_or_insert(op);
+                    } else if SHORT_OPTIONS_WITH_VALUE.contains(&short) {
+                        let rest = shorts.as_str();
+                        let value = if rest.is_empty() {
+                            arguments.next().cloned()
+                        } else {
+                            Some(rest.to_string())
+                        };
+                        flags.push(Flag::new(format!("-{short}"), value));
+                        break;
+                    } else {
+                        flags.push(Flag::new(format!("-{short}"), None));
+                    }
+                }
+            } else {
+                targets.push(arg.clone());
+            }
+        }
+
+        Ok(Self {
+            argv: argv.into_boxed_slice(),
+            operation,
+            flags: flags.into_boxed_slice(),
+            targets: targets.into_boxed_slice(),
+        })
+    }
+}
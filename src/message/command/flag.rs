@@ -0,0 +1,26 @@
+/// A flag passed to pacman, optionally carrying a value.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Flag {
+    name: String,
+    value: Option<String>,
+}
+
+impl Flag {
+    /// Create a new flag.
+    #[must_use]
+    pub(crate) const fn new(name: String, value: Option<String>) -> Self {
+        Self { name, value }
+    }
+
+    /// Return the flag's name including its leading dashes, e.g. `--needed` or `-y`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the flag's value, e.g. the glob of `--overwrite`.
+    #[must_use]
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+}
@@ -0,0 +1,50 @@
+/// Operations that pacman can perform.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Operation {
+    /// Operate on the package database (`-D`).
+    Database,
+    /// Query the files database (`-F`).
+    Files,
+    /// Query the local package database (`-Q`).
+    Query,
+    /// Remove packages (`-R`).
+    Remove,
+    /// Synchronize packages (`-S`).
+    Sync,
+    /// Check dependencies (`-T`).
+    DepTest,
+    /// Upgrade or add packages from files (`-U`).
+    Upgrade,
+}
+
+impl Operation {
+    /// Return the operation selected by the given short option.
+    #[must_use]
+    pub const fn from_short(option: char) -> Option<Self> {
+        match option {
+            'D' => Some(Self::Database),
+            'F' => Some(Self::Files),
+            'Q' => Some(Self::Query),
+            'R' => Some(Self::Remove),
+            'S' => Some(Self::Sync),
+            'T' => Some(Self::DepTest),
+            'U' => Some(Self::Upgrade),
+            _ => None,
+        }
+    }
+
+    /// Return the operation selected by the given long option without its leading dashes.
+    #[must_use]
+    pub fn from_long(option: &str) -> Option<Self> {
+        match option {
+            "database" => Some(Self::Database),
+            "files" => Some(Self::Files),
+            "query" => Some(Self::Query),
+            "remove" => Some(Self::Remove),
+            "sync" => Some(Self::Sync),
+            "deptest" => Some(Self::DepTest),
+            "upgrade" => Some(Self::Upgrade),
+            _ => None,
+        }
+    }
+}
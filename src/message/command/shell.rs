@@ -0,0 +1,54 @@
+/// Split a command line into its arguments using shell-style quoting.
+///
+/// Returns `None` if a quote is left unterminated.
+pub fn split(text: &str) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = text.chars();
+
+    while let Some(chr) = chars.next() {
+        match chr {
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        chr => arg.push(chr),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            chr @ ('"' | '\\' | '$' | '`') => arg.push(chr),
+                            chr => {
+                                arg.push('\\');
+                                arg.push(chr);
+                            }
+                        },
+                        chr => arg.push(chr),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(chr) = chars.next() {
+                    current.get_or_insert_with(String::new).push(chr);
+                }
+            }
+            chr if chr.is_whitespace() => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            chr => current.get_or_insert_with(String::new).push(chr),
+        }
+    }
+
+    args.extend(current);
+    Some(args)
+}
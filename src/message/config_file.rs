@@ -0,0 +1,42 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::ConfigFileRef;
+
+/// A configuration file that has been installed or saved under a new path,
+/// such as a `.pacnew` or `.pacsave` file.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ConfigFile {
+    original: PathBuf,
+    new_path: PathBuf,
+}
+
+impl ConfigFile {
+    /// Create a new config file.
+    #[must_use]
+    pub(crate) const fn new(original: PathBuf, new_path: PathBuf) -> Self {
+        Self { original, new_path }
+    }
+
+    /// Return the path of the original file.
+    #[must_use]
+    pub fn original(&self) -> &Path {
+        &self.original
+    }
+
+    /// Return the path that the file has been installed or saved as.
+    #[must_use]
+    pub fn new_path(&self) -> &Path {
+        &self.new_path
+    }
+}
+
+impl FromStr for ConfigFile {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        ConfigFileRef::pacnew(text)
+            .or_else(|_| ConfigFileRef::pacsave(text))
+            .map(|config_file| config_file.to_owned())
+    }
+}
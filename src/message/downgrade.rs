@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use crate::DowngradeRef;
+
+/// Represents a package downgrade.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Downgrade {
+    name: String,
+    old_version: String,
+    new_version: String,
+}
+
+impl Downgrade {
+    /// Create a new downgrade.
+    #[must_use]
+    pub(crate) const fn new(name: String, old_version: String, new_version: String) -> Self {
+        Self {
+            name,
+            old_version,
+            new_version,
+        }
+    }
+
+    /// Return the name of the downgraded package.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the old version of the package.
+    #[must_use]
+    pub fn old_version(&self) -> &str {
+        &self.old_version
+    }
+
+    /// Return the new version of the package.
+    #[must_use]
+    pub fn new_version(&self) -> &str {
+        &self.new_version
+    }
+}
+
+impl FromStr for Downgrade {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        DowngradeRef::try_from(text).map(|downgrade| downgrade.to_owned())
+    }
+}
@@ -1,11 +1,6 @@
-use std::cell::LazyCell;
 use std::str::FromStr;
 
-use regex::Regex;
-
-const REGEX_STR: &str = r"^(.+) \((.+)\)$";
-#[allow(clippy::declare_interior_mutable_const)]
-const REGEX: LazyCell<Regex> = LazyCell::new(|| Regex::new(REGEX_STR).expect("malformed regex"));
+use crate::PackageRef;
 
 /// Represents information about a package.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -15,6 +10,12 @@ pub struct Package {
 }
 
 impl Package {
+    /// Create a new package.
+    #[must_use]
+    pub(crate) const fn new(name: String, version: String) -> Self {
+        Self { name, version }
+    }
+
     /// Return the package's name.
     #[must_use]
     pub fn name(&self) -> &str {
@@ -32,16 +33,6 @@ impl FromStr for Package {
     type Err = String;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        #[allow(clippy::borrow_interior_mutable_const)]
-        let (_, [name, version]) = REGEX
-            .captures_iter(text)
-            .map(|capture| capture.extract())
-            .next()
-            .ok_or_else(|| text.to_string())?;
-
-        Ok(Self {
-            name: name.to_string(),
-            version: version.to_string(),
-        })
+        PackageRef::try_from(text).map(|package| package.to_owned())
     }
 }
@@ -1,11 +1,6 @@
-use std::cell::LazyCell;
 use std::str::FromStr;
 
-use regex::Regex;
-
-const REGEX_STR: &str = r"^(.+) \((.+) -> (.+)\)$";
-#[allow(clippy::declare_interior_mutable_const)]
-const REGEX: LazyCell<Regex> = LazyCell::new(|| Regex::new(REGEX_STR).expect("malformed regex"));
+use crate::UpgradeRef;
 
 /// Represents a package upgrade.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -16,6 +11,16 @@ pub struct Upgrade {
 }
 
 impl Upgrade {
+    /// Create a new upgrade.
+    #[must_use]
+    pub(crate) const fn new(name: String, old_version: String, new_version: String) -> Self {
+        Self {
+            name,
+            old_version,
+            new_version,
+        }
+    }
+
     /// Return the name of the upgraded package.
     #[must_use]
     pub fn name(&self) -> &str {
@@ -39,17 +44,6 @@ impl FromStr for Upgrade {
     type Err = String;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        #[allow(clippy::borrow_interior_mutable_const)]
-        let (_, [name, old_version, new_version]) = REGEX
-            .captures_iter(text)
-            .map(|capture| capture.extract())
-            .next()
-            .ok_or_else(|| text.to_string())?;
-
-        Ok(Self {
-            name: name.to_string(),
-            old_version: old_version.to_string(),
-            new_version: new_version.to_string(),
-        })
+        UpgradeRef::try_from(text).map(|upgrade| upgrade.to_owned())
     }
 }
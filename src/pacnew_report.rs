@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::{Entry, Message};
+
+/// Configuration files that got a `.pacnew` across the history of a logfile.
+///
+/// Collect it from entries, e.g. `reader.filter_map(Result::ok).collect::<PacnewReport>()`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PacnewReport {
+    files: BTreeMap<PathBuf, Vec<DateTime<FixedOffset>>>,
+}
+
+impl PacnewReport {
+    /// Return the amount of configuration files that got a `.pacnew`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Return whether no configuration file got a `.pacnew`.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Return the original paths of the configuration files along with the times
+    /// at which they got a `.pacnew`, ordered by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &[DateTime<FixedOffset>])> {
+        self.files
+            .iter()
+            .map(|(path, times)| (path.as_path(), times.as_slice()))
+    }
+
+    /// Return the times at which the given configuration file got a `.pacnew`.
+    #[must_use]
+    pub fn occurrences(&self, path: &Path) -> &[DateTime<FixedOffset>] {
+        self.files.get(path).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Extend<Entry> for PacnewReport {
+    fn extend<T: IntoIterator<Item = Entry>>(&mut self, entries: T) {
+        for entry in entries {
+            if let Message::Pacnew(config_file) = entry.message() {
+                self.files
+                    .entry(config_file.original().to_path_buf())
+                    .or_default()
+                    .push(entry.timestamp());
+            }
+        }
+    }
+}
+
+impl FromIterator<Entry> for PacnewReport {
+    fn from_iter<T: IntoIterator<Item = Entry>>(entries: T) -> Self {
+        let mut report = Self::default();
+        report.extend(entries);
+        report
+    }
+}
@@ -0,0 +1,228 @@
+use std::fs::{read, File};
+use std::ops::Range;
+use std::path::Path;
+use std::str::from_utf8;
+
+use chrono::{FixedOffset, Offset, Utc};
+use memmap2::Mmap;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::reader::trim_newline;
+use crate::{Entry, EntryRef, LogReader, ReadError, Transaction, Transactions};
+
+const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Parses a memory-mapped logfile in parallel.
+///
+/// The file is split into chunks that start with a valid entry, so that continuation lines
+/// are never separated from their entry. The chunks are parsed in parallel and the results are
+/// returned in the order of the file, just as a [`LogReader`] would yield them.
+///
+/// A mapped file must not be truncated while the reader exists, e.g. by logrotate's `copytruncate`,
+/// since accessing the pages past its new end terminates the process with `SIGBUS`.
+/// Use [`ParallelReader::read`] for files that may be truncated in the meantime.
+#[derive(Debug)]
+pub struct ParallelReader {
+    data: Data,
+    offset: FixedOffset,
+    chunk_size: usize,
+}
+
+impl ParallelReader {
+    /// Memory-map the logfile at the given path.
+    ///
+    /// The caller must make sure that the file is not truncated while the reader exists.
+    /// Appending to the file is fine, since it does not affect the mapped range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be opened or mapped.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: The mapping is only ever read. It stays valid as long as the file is not truncated,
+        // which the caller must ensure as documented.
+        #[allow(unsafe_code)]
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Self::new(Data::Mapped(mmap)))
+    }
+
+    /// Read the logfile at the given path into memory.
+    ///
+    /// Unlike [`ParallelReader::open`], this is safe for files that may be truncated while being parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be read.
+    pub fn read(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        read(path).map(|bytes| Self::new(Data::Buffered(bytes)))
+    }
+
+    fn new(data: Data) -> Self {
+        Self {
+            data,
+            offset: Utc.fix(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Assume the given offset for timestamps without a time zone.
+    #[must_use]
+    pub const fn with_default_offset(mut self, offset: FixedOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the approximate size of the chunks in bytes.
+    ///
+    /// Defaults to one MiB.
+    #[must_use]
+    pub const fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = if chunk_size == 0 { 1 } else { chunk_size };
+        self
+    }
+
+    /// Parse all entries of the logfile.
+    ///
+    /// Line numbers and offsets of [`ReadError`]s refer to the whole file.
+    #[must_use]
+    pub fn entries(&self) -> Vec<Result<Entry, ReadError>> {
+        let chunks: Vec<_> = self
+            .chunks()
+            .into_par_iter()
+            .map(|range| self.parse(range))
+            .collect();
+        let mut lines = 0;
+        let mut entries = Vec::new();
+
+        for (results, count) in chunks {
+            entries.extend(
+                results
+                    .into_iter()
+                    .map(|result| result.map_err(|error| error.shifted(lines, 0))),
+            );
+            lines += count;
+        }
+
+        entries
+    }
+
+    /// Parse all entries of the logfile and group them into transactions.
+    ///
+    /// Entries that could not be parsed are skipped.
+    #[must_use]
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.entries()
+            .into_iter()
+            .filter_map(Result::ok)
+            .transactions()
+            .collect()
+    }
+
+    /// Parse a chunk, returning its results and amount of lines.
+    fn parse(&self, range: Range<usize>) -> (Vec<Result<Entry, ReadError>>, usize) {
+        let offset = range.start as u64;
+        let mut reader = LogReader::new(&self.data.bytes()[range]).with_default_offset(self.offset);
+        let results = reader
+            .by_ref()
+            .map(|result| result.map_err(|error| error.shifted(0, offset)))
+            .collect();
+        (results, reader.summary().lines())
+    }
+
+    /// Split the file into chunks that each start with a valid entry.
+    fn chunks(&self) -> Vec<Range<usize>> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+
+        while start < self.data.bytes().len() {
+            let end = self.next_entry(start.saturating_add(self.chunk_size));
+            chunks.push(start..end);
+            start = end;
+        }
+
+        chunks
+    }
+
+    /// Return the offset of the first line starting a valid entry after the given offset.
+    fn next_entry(&self, offset: usize) -> usize {
+        let Some(mut rest) = self.data.bytes().get(offset..) else {
+            return self.data.bytes().len();
+        };
+
+        while let Some(newline) = rest.iter().position(|&byte| byte == b'\n') {
+            rest = &rest[newline + 1..];
+            let line = rest
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .map_or(rest, |end| &rest[..=end]);
+
+            if from_utf8(trim_newline(line)).is_ok_and(|line| EntryRef::try_from(line).is_ok()) {
+                return self.data.bytes().len() - rest.len();
+            }
+        }
+
+        self.data.bytes().len()
+    }
+}
+
+/// The contents of a logfile.
+#[derive(Debug)]
+enum Data {
+    Mapped(Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl Data {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Buffered(bytes) => bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParallelReader;
+    use crate::fixture::{TempDir, LOG};
+    use crate::{Entry, LogReader, ReadError, Transactions};
+
+    /// Return the entries and the positions of the errors.
+    fn positions(
+        results: impl IntoIterator<Item = Result<Entry, ReadError>>,
+    ) -> Vec<Result<Entry, (usize, u64)>> {
+        results
+            .into_iter()
+            .map(|result| result.map_err(|error| (error.line(), error.offset())))
+            .collect()
+    }
+
+    #[test]
+    fn yields_the_same_entries_as_log_reader() {
+        let log = format!("{LOG}[2024-03-05T00:00:00+0100] [ALPM] installed\n{LOG}");
+        let dir = TempDir::new("parallel");
+        let path = dir.write("pacman.log", &log);
+        let expected = positions(LogReader::new(log.as_bytes()));
+        let transactions: Vec<_> = LogReader::new(log.as_bytes())
+            .filter_map(Result::ok)
+            .transactions()
+            .collect();
+
+        for chunk_size in [0, 1, 10, 100, 1000, log.len(), usize::MAX] {
+            let reader = ParallelReader::open(&path)
+                .unwrap()
+                .with_chunk_size(chunk_size);
+            assert_eq!(
+                positions(reader.entries()),
+                expected,
+                "chunk size {chunk_size}"
+            );
+            assert_eq!(reader.transactions(), transactions);
+
+            let reader = ParallelReader::read(&path)
+                .unwrap()
+                .with_chunk_size(chunk_size);
+            assert_eq!(positions(reader.entries()), expected);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+use chrono::{DateTime, FixedOffset};
+
+pub use error::{ReadError, ReadErrorKind};
+pub use parser::trim_newline;
+pub use summary::ReadSummary;
+pub use window::Window;
+
+use crate::index::inode;
+use crate::{Checkpoint, Entry, RotatedReader};
+pub use parser::Parser;
+
+mod error;
+mod parser;
+mod seek;
+mod summary;
+mod window;
+
+/// Reads entries from a pacman logfile.
+///
+/// Continuation lines are appended to the previous entry and invalid UTF-8 is replaced lossily.
+/// Lines that cannot be parsed are reported as [`ReadError`]s along with their position.
+#[derive(Debug)]
+pub struct LogReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    parser: Parser,
+    done: bool,
+}
+
+impl LogReader<BufReader<File>> {
+    /// Open the logfile at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be opened.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        File::open(path).map(|file| Self::new(BufReader::new(file)))
+    }
+
+    /// Open the logfile at the given path and continue reading after the given checkpoint.
+    ///
+    /// If the logfile has been replaced or truncated since the checkpoint was taken,
+    /// it is read from the beginning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be opened or seeked.
+    pub fn resume(path: impl AsRef<Path>, checkpoint: &Checkpoint) -> std::io::Result<Self> {
+        let mut file = File::open(path)?;
+        let metadata = file.metadata()?;
+
+        if !checkpoint.applies_to(&metadata) {
+            return Ok(Self::new(BufReader::new(file)));
+        }
+
+        file.seek(SeekFrom::Start(checkpoint.offset()))?;
+        let mut reader = Self::new(BufReader::new(file));
+        reader.parser.resume(checkpoint);
+        Ok(reader)
+    }
+
+    /// Return a checkpoint after the entries yielded so far.
+    ///
+    /// Lines that have been read but not yet yielded as part of an entry are read again after resuming.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the metadata of the file could not be read.
+    pub fn checkpoint(&self) -> std::io::Result<Checkpoint> {
+        Ok(self
+            .parser
+            .checkpoint(inode(&self.reader.get_ref().metadata()?)))
+    }
+}
+
+impl LogReader<RotatedReader> {
+    /// Open the logfile at the given path along with its rotated and possibly compressed siblings.
+    ///
+    /// See [`RotatedReader`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the parent directory could not be read.
+    pub fn open_rotated(base: impl AsRef<Path>) -> std::io::Result<Self> {
+        RotatedReader::open(base).map(Self::new)
+    }
+}
+
+impl<R> LogReader<R>
+where
+    R: BufRead,
+{
+    /// Create a new reader from any buffered reader.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            parser: Parser::default(),
+            done: false,
+        }
+    }
+
+    /// Assume the given offset for timestamps without a time zone.
+    #[must_use]
+    pub fn with_default_offset(mut self, offset: FixedOffset) -> Self {
+        self.parser.set_default_offset(offset);
+        self
+    }
+
+    /// Return a summary of the lines read so far.
+    #[must_use]
+    pub const fn summary(&self) -> &ReadSummary {
+        self.parser.summary()
+    }
+
+    /// Return the byte offset at which the most recently yielded entry starts.
+    #[must_use]
+    pub const fn entry_offset(&self) -> u64 {
+        self.parser.entry_offset()
+    }
+}
+
+impl<R> LogReader<R>
+where
+    R: BufRead + Seek,
+{
+    /// Seek to the first entry at or after the given timestamp and return its byte offset.
+    ///
+    /// The position is found by binary search over the file, so the timestamps
+    /// of its entries are assumed to be ordered.
+    /// Line numbers of subsequent [`ReadError`]s are counted from the new position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the underlying reader could not be read or seeked.
+    pub fn seek_to(&mut self, timestamp: DateTime<FixedOffset>) -> std::io::Result<u64> {
+        let offset =
+            seek::first_at_or_after(&mut self.reader, timestamp, self.parser.default_offset())?;
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.buffer.clear();
+        self.parser.reset(offset);
+        self.done = false;
+        Ok(offset)
+    }
+
+    /// Return the entries at or after `since` and before `until`.
+    ///
+    /// See [`LogReader::seek_to`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the underlying reader could not be read or seeked.
+    pub fn between(
+        mut self,
+        since: DateTime<FixedOffset>,
+        until: DateTime<FixedOffset>,
+    ) -> std::io::Result<Window<R>> {
+        self.seek_to(since)?;
+        Ok(Window::new(self, until))
+    }
+}
+
+impl<R> Iterator for LogReader<R>
+where
+    R: BufRead,
+{
+    type Item = Result<Entry, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.parser.queued() {
+            return Some(Err(error));
+        }
+
+        while !self.done {
+            self.buffer.clear();
+
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    if let Some(result) = self.parser.line(&self.buffer) {
+                        return Some(result);
+                    }
+                }
+                Err(error) => {
+                    self.done = true;
+                    return Some(self.parser.io_error(error, &self.buffer));
+                }
+            }
+        }
+
+        self.parser.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogReader;
+    use crate::fixture::TempDir;
+    use crate::{Checkpoint, Error, Message, ReadErrorKind};
+
+    const LOG: &[u8] = b"[2024-03-04T12:34:56+0100] [ALPM] transaction started\r\n\
+[2024-03-04T12:34:56+0100] [ALPM] installed linux (6.7.8.arch1-1)\n\
+[2024-03-04T12:34:56+0100] [ALPM-SCRIPTLET] ==> Building image\n\
+==> Image generation successful\n\
+[2024-03-04T12:34:57+0100] [ALPM] installed\n\
+[2024-03-04T12:34:57+0100] [ALPM] transaction completed";
+
+    #[test]
+    fn reads_entries_and_reports_positioned_errors() {
+        let mut reader = LogReader::new(LOG);
+
+        let entry = reader.next().unwrap().unwrap();
+        assert_eq!(entry.message(), &Message::TransactionStarted);
+        assert_eq!(reader.entry_offset(), 0);
+
+        let entry = reader.next().unwrap().unwrap();
+        assert!(
+            matches!(entry.message(), Message::Installed(package) if package.name() == "linux")
+        );
+        assert_eq!(reader.entry_offset(), 55);
+
+        let entry = reader.next().unwrap().unwrap();
+        assert_eq!(
+            entry.message(),
+            &Message::Other("==> Building image\n==> Image generation successful".to_string())
+        );
+
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.line(), 5);
+        assert_eq!(
+            error.bytes(),
+            b"[2024-03-04T12:34:57+0100] [ALPM] installed"
+        );
+        assert!(matches!(
+            error.kind(),
+            ReadErrorKind::Parse(Error::InvalidMessage(_))
+        ));
+
+        let entry = reader.next().unwrap().unwrap();
+        assert_eq!(entry.message(), &Message::TransactionCompleted);
+        assert!(reader.next().is_none());
+
+        let summary = reader.summary();
+        assert_eq!(summary.lines(), 6);
+        assert_eq!(summary.entries(), 4);
+        assert_eq!(summary.continuation_lines(), 1);
+        assert_eq!(summary.invalid_messages(), 1);
+    }
+
+    #[test]
+    fn replaces_invalid_utf8() {
+        let mut reader =
+            LogReader::new(&b"[2024-03-04T12:34:56+0100] [ALPM-SCRIPTLET] caf\xe9\n"[..]);
+        let entry = reader.next().unwrap().unwrap();
+        assert_eq!(entry.message(), &Message::Other("caf\u{fffd}".to_string()));
+        assert_eq!(reader.summary().lossy_lines(), 1);
+    }
+
+    #[test]
+    fn counts_lines_from_the_start_of_the_file_after_resuming() {
+        let dir = TempDir::new("reader-resume-lines");
+        let path = dir.write("pacman.log", LOG);
+        let mut reader = LogReader::open(&path).unwrap();
+        reader.next().unwrap().unwrap();
+        reader.next().unwrap().unwrap();
+
+        let checkpoint: Checkpoint = reader.checkpoint().unwrap().to_string().parse().unwrap();
+        assert_eq!(checkpoint.line(), 2);
+
+        let mut reader = LogReader::resume(&path, &checkpoint).unwrap();
+        reader.next().unwrap().unwrap();
+        assert_eq!(reader.next().unwrap().unwrap_err().line(), 5);
+    }
+}
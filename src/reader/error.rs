@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use crate::Error;
+
+/// An error that occurred while reading a logfile, along with its position.
+#[derive(Debug)]
+pub struct ReadError {
+    line: usize,
+    offset: u64,
+    bytes: Vec<u8>,
+    kind: ReadErrorKind,
+}
+
+impl ReadError {
+    /// Create a new read error.
+    #[must_use]
+    pub(crate) const fn new(line: usize, offset: u64, bytes: Vec<u8>, kind: ReadErrorKind) -> Self {
+        Self {
+            line,
+            offset,
+            bytes,
+            kind,
+        }
+    }
+
+    /// Move the error by the given amount of lines and bytes.
+    #[cfg(feature = "rayon")]
+    pub(crate) const fn shifted(mut self, lines: usize, offset: u64) -> Self {
+        self.line += lines;
+        self.offset += offset;
+        self
+    }
+
+    /// Return the one-based number of the offending line.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the byte offset of the start of the offending line.
+    #[must_use]
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Return the raw bytes of the offending line without its line terminator.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Return the kind of error.
+    #[must_use]
+    pub const fn kind(&self) -> &ReadErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} (byte {}): {}",
+            self.line, self.offset, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+/// Kinds of errors that can occur while reading a logfile.
+#[derive(Debug)]
+pub enum ReadErrorKind {
+    /// The underlying reader failed.
+    Io(std::io::Error),
+    /// The line could not be parsed.
+    Parse(Error),
+}
+
+impl Display for ReadErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "I/O error: {error}"),
+            Self::Parse(error) => write!(f, "parse error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ReadErrorKind {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+        }
+    }
+}
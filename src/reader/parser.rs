@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::assembler::{Assembler, Push};
+use crate::{Checkpoint, Entry, Message, ReadError, ReadErrorKind, ReadSummary};
+
+/// State machine that turns raw lines into entries, independently of how the lines are read.
+#[derive(Debug, Default)]
+pub struct Parser {
+    assembler: Assembler,
+    line: usize,
+    pending_line: usize,
+    offset: u64,
+    pending_offset: u64,
+    entry_offset: u64,
+    timestamp: Option<DateTime<FixedOffset>>,
+    context: Option<u64>,
+    transaction: Option<(u64, u64)>,
+    queued: Option<ReadError>,
+    summary: ReadSummary,
+}
+
+impl Parser {
+    /// Assume the given offset for timestamps without a time zone.
+    pub fn set_default_offset(&mut self, offset: FixedOffset) {
+        self.assembler = Assembler::new(offset);
+    }
+
+    /// Return the offset assumed for timestamps without a time zone.
+    pub const fn default_offset(&self) -> FixedOffset {
+        self.assembler.offset()
+    }
+
+    /// Return a summary of the lines parsed so far.
+    pub const fn summary(&self) -> &ReadSummary {
+        &self.summary
+    }
+
+    /// Return the byte offset at which the most recently yielded entry starts.
+    pub const fn entry_offset(&self) -> u64 {
+        self.entry_offset
+    }
+
+    /// Return a checkpoint after the entries yielded so far.
+    pub const fn checkpoint(&self, inode: u64) -> Checkpoint {
+        Checkpoint::new(
+            inode,
+            if self.assembler.is_pending() {
+                self.pending_offset
+            } else {
+                self.offset
+            },
+            if self.assembler.is_pending() {
+                self.pending_line
+            } else {
+                self.line
+            },
+            self.timestamp,
+            self.context,
+            self.transaction,
+        )
+    }
+
+    /// Continue after the given checkpoint.
+    pub fn resume(&mut self, checkpoint: &Checkpoint) {
+        self.reset(checkpoint.offset());
+        self.line = checkpoint.line();
+        self.pending_line = checkpoint.line();
+        self.timestamp = checkpoint.timestamp();
+        self.context = checkpoint.context();
+        self.transaction = checkpoint
+            .transaction()
+            .map(|span| (span.context(), span.start()));
+    }
+
+    /// Discard all pending state and continue at the given byte offset.
+    pub fn reset(&mut self, offset: u64) {
+        self.assembler.finish();
+        self.line = 0;
+        self.pending_line = 0;
+        self.offset = offset;
+        self.pending_offset = offset;
+        self.context = None;
+        self.transaction = None;
+        self.queued = None;
+    }
+
+    /// Return the error queued after the previously yielded entry, if any.
+    pub const fn queued(&mut self) -> Option<ReadError> {
+        self.queued.take()
+    }
+
+    /// Parse a line including its line terminator.
+    ///
+    /// Returns an entry once it has been completed by the start of the next one.
+    pub fn line(&mut self, buffer: &[u8]) -> Option<Result<Entry, ReadError>> {
+        let offset = self.offset;
+        self.line += 1;
+        self.offset += buffer.len() as u64;
+        self.summary.record_line();
+        let bytes = trim_newline(buffer);
+        let text = String::from_utf8_lossy(bytes);
+
+        if matches!(text, Cow::Owned(_)) {
+            self.summary.record_lossy_line();
+        }
+
+        match self.assembler.push(&text) {
+            Push::Started(Some(entry)) => {
+                let entry = self.emit(entry);
+                self.pending_line = self.line - 1;
+                self.pending_offset = offset;
+                Some(Ok(entry))
+            }
+            Push::Started(None) => {
+                self.pending_line = self.line - 1;
+                self.pending_offset = offset;
+                None
+            }
+            Push::Continued => {
+                self.summary.record_continuation_line();
+                None
+            }
+            Push::Failed(error) => {
+                let error = ReadError::new(
+                    self.line,
+                    offset,
+                    bytes.to_vec(),
+                    ReadErrorKind::Parse(error),
+                );
+                Some(self.fail(error))
+            }
+        }
+    }
+
+    /// Report an I/O error that occurred while reading the next line.
+    pub fn io_error(&mut self, error: std::io::Error, buffer: &[u8]) -> Result<Entry, ReadError> {
+        let error = ReadError::new(
+            self.line + 1,
+            self.offset,
+            buffer.to_vec(),
+            ReadErrorKind::Io(error),
+        );
+        self.fail(error)
+    }
+
+    /// Return the last pending entry, if any.
+    pub fn finish(&mut self) -> Option<Result<Entry, ReadError>> {
+        self.assembler.finish().map(|entry| Ok(self.emit(entry)))
+    }
+
+    /// Return the pending entry, if any, and queue the given error after it.
+    fn fail(&mut self, error: ReadError) -> Result<Entry, ReadError> {
+        self.summary.record_error(&error);
+
+        if let Some(entry) = self.assembler.finish() {
+            self.queued.replace(error);
+            Ok(self.emit(entry))
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Record the pending entry as yielded and track the transaction it belongs to.
+    fn emit(&mut self, entry: Entry) -> Entry {
+        self.summary.record_entry();
+        self.entry_offset = self.pending_offset;
+        self.timestamp = Some(entry.timestamp());
+
+        match entry.message() {
+            Message::Running(_) => self.context = Some(self.entry_offset),
+            Message::SynchronizingPackageLists | Message::StartingFullSystemUpgrade => {
+                self.context.get_or_insert(self.entry_offset);
+            }
+            Message::TransactionStarted => {
+                let context = self.context.take().unwrap_or(self.entry_offset);
+                self.transaction = Some((context, self.entry_offset));
+            }
+            Message::TransactionCompleted | Message::TransactionInterrupted => {
+                self.transaction = None;
+            }
+            _ => {}
+        }
+
+        entry
+    }
+}
+
+/// Strip a trailing line terminator.
+pub fn trim_newline(bytes: &[u8]) -> &[u8] {
+    let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    bytes.strip_suffix(b"\r").unwrap_or(bytes)
+}
@@ -0,0 +1,124 @@
+use std::io::{BufRead, Seek, SeekFrom};
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::Entry;
+
+/// Find the offset of the first entry at or after the given timestamp using binary search.
+///
+/// This assumes that the timestamps of the entries are ordered.
+pub fn first_at_or_after<R>(
+    reader: &mut R,
+    timestamp: DateTime<FixedOffset>,
+    offset: FixedOffset,
+) -> std::io::Result<u64>
+where
+    R: BufRead + Seek,
+{
+    let mut low = 0;
+    let mut high = reader.seek(SeekFrom::End(0))?;
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        match probe(reader, middle, offset)? {
+            Some((_, end, probed)) if probed < timestamp => low = end,
+            _ => high = middle,
+        }
+    }
+
+    Ok(match probe(reader, low, offset)? {
+        Some((start, _, _)) => start,
+        None => reader.seek(SeekFrom::End(0))?,
+    })
+}
+
+/// Return the start, end and timestamp of the first entry starting at or after the given position.
+fn probe<R>(
+    reader: &mut R,
+    position: u64,
+    offset: FixedOffset,
+) -> std::io::Result<Option<(u64, u64, DateTime<FixedOffset>)>>
+where
+    R: BufRead + Seek,
+{
+    let mut buffer = Vec::new();
+    let mut start = position;
+
+    if position > 0 {
+        reader.seek(SeekFrom::Start(position - 1))?;
+        start += reader.read_until(b'\n', &mut buffer)? as u64 - 1;
+    } else {
+        reader.seek(SeekFrom::Start(0))?;
+    }
+
+    loop {
+        buffer.clear();
+        let size = reader.read_until(b'\n', &mut buffer)? as u64;
+
+        if size == 0 {
+            return Ok(None);
+        }
+
+        let text = String::from_utf8_lossy(super::trim_newline(&buffer));
+
+        if let Ok(entry) = Entry::from_str_with_offset(&text, offset) {
+            return Ok(Some((start, start + size, entry.timestamp())));
+        }
+
+        start += size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use chrono::{DateTime, Duration};
+
+    use crate::fixture::LOG;
+    use crate::LogReader;
+
+    #[test]
+    fn seeks_to_the_first_entry_at_or_after_a_timestamp() {
+        let mut reader = LogReader::new(LOG.as_bytes());
+        let mut entries = Vec::new();
+
+        while let Some(entry) = reader.next() {
+            entries.push((reader.entry_offset(), entry.unwrap().timestamp()));
+        }
+
+        for &(_, timestamp) in &entries {
+            let expected = entries
+                .iter()
+                .find(|&&(_, other)| other >= timestamp)
+                .map(|&(offset, _)| offset);
+            let mut reader = LogReader::new(Cursor::new(LOG));
+            assert_eq!(Some(reader.seek_to(timestamp).unwrap()), expected);
+
+            let later = timestamp + Duration::milliseconds(500);
+            let expected = entries
+                .iter()
+                .find(|&&(_, other)| other >= later)
+                .map_or(LOG.len() as u64, |&(offset, _)| offset);
+            assert_eq!(reader.seek_to(later).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn yields_entries_between_timestamps() {
+        let since = DateTime::parse_from_rfc3339("2024-03-02T10:05:00+01:00").unwrap();
+        let until = DateTime::parse_from_rfc3339("2024-03-02T10:06:00+01:00").unwrap();
+        let window: Vec<_> = LogReader::new(Cursor::new(LOG))
+            .between(since, until)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        let expected: Vec<_> = LogReader::new(LOG.as_bytes())
+            .map(Result::unwrap)
+            .filter(|entry| (since..until).contains(&entry.timestamp()))
+            .collect();
+        assert_eq!(window.len(), 4);
+        assert_eq!(window, expected);
+    }
+}
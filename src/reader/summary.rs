@@ -0,0 +1,97 @@
+use crate::Error;
+
+use super::{ReadError, ReadErrorKind};
+
+/// Statistics about the lines read by a [`LogReader`](crate::LogReader).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ReadSummary {
+    lines: usize,
+    entries: usize,
+    continuation_lines: usize,
+    lossy_lines: usize,
+    malformed_entries: usize,
+    invalid_timestamps: usize,
+    invalid_messages: usize,
+    io_errors: usize,
+}
+
+impl ReadSummary {
+    /// Return the amount of lines read.
+    #[must_use]
+    pub const fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Return the amount of entries yielded.
+    #[must_use]
+    pub const fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Return the amount of lines that were appended to the previous entry.
+    #[must_use]
+    pub const fn continuation_lines(&self) -> usize {
+        self.continuation_lines
+    }
+
+    /// Return the amount of lines that contained invalid UTF-8 and were decoded lossily.
+    #[must_use]
+    pub const fn lossy_lines(&self) -> usize {
+        self.lossy_lines
+    }
+
+    /// Return the amount of lines skipped because they were malformed.
+    #[must_use]
+    pub const fn malformed_entries(&self) -> usize {
+        self.malformed_entries
+    }
+
+    /// Return the amount of lines skipped because of an invalid timestamp.
+    #[must_use]
+    pub const fn invalid_timestamps(&self) -> usize {
+        self.invalid_timestamps
+    }
+
+    /// Return the amount of lines skipped because of an invalid message.
+    #[must_use]
+    pub const fn invalid_messages(&self) -> usize {
+        self.invalid_messages
+    }
+
+    /// Return the amount of I/O errors.
+    #[must_use]
+    pub const fn io_errors(&self) -> usize {
+        self.io_errors
+    }
+
+    /// Return the total amount of skipped lines.
+    #[must_use]
+    pub const fn skipped(&self) -> usize {
+        self.malformed_entries + self.invalid_timestamps + self.invalid_messages + self.io_errors
+    }
+
+    pub(crate) const fn record_line(&mut self) {
+        self.lines += 1;
+    }
+
+    pub(crate) const fn record_entry(&mut self) {
+        self.entries += 1;
+    }
+
+    pub(crate) const fn record_continuation_line(&mut self) {
+        self.continuation_lines += 1;
+    }
+
+    pub(crate) const fn record_lossy_line(&mut self) {
+        self.lossy_lines += 1;
+    }
+
+    pub(crate) const fn record_error(&mut self, error: &ReadError) {
+        match error.kind() {
+            ReadErrorKind::Io(_) => self.io_errors += 1,
+            ReadErrorKind::Parse(Error::MalformedEntry(_)) => self.malformed_entries += 1,
+            ReadErrorKind::Parse(Error::InvalidTimestamp(_)) => self.invalid_timestamps += 1,
+            ReadErrorKind::Parse(Error::InvalidMessage(_)) => self.invalid_messages += 1,
+        }
+    }
+}
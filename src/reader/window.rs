@@ -0,0 +1,51 @@
+use std::io::BufRead;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::{Entry, LogReader, ReadError};
+
+/// Entries of a [`LogReader`] up to, but excluding, a given timestamp.
+#[derive(Debug)]
+pub struct Window<R> {
+    reader: LogReader<R>,
+    until: DateTime<FixedOffset>,
+    done: bool,
+}
+
+impl<R> Window<R> {
+    /// Create a new window.
+    pub(crate) const fn new(reader: LogReader<R>, until: DateTime<FixedOffset>) -> Self {
+        Self {
+            reader,
+            until,
+            done: false,
+        }
+    }
+
+    /// Return the underlying reader.
+    #[must_use]
+    pub const fn reader(&self) -> &LogReader<R> {
+        &self.reader
+    }
+}
+
+impl<R> Iterator for Window<R>
+where
+    R: BufRead,
+{
+    type Item = Result<Entry, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.reader.next()? {
+            Ok(entry) if entry.timestamp() >= self.until => {
+                self.done = true;
+                None
+            }
+            item => Some(item),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use chrono::{FixedOffset, Offset, Utc};
+
+use crate::reader::trim_newline;
+use crate::{Entry, Error, ReadError, ReadErrorKind, Transaction, Transactions};
+
+const BLOCK_SIZE: u64 = 64 * 1024;
+
+/// Reads entries of a logfile backwards, from the newest to the oldest one.
+///
+/// The file is read in blocks from its end, so that only its tail is touched
+/// when only the most recent entries are consumed.
+/// Line numbers of [`ReadError`]s are counted from the end of the file.
+#[derive(Debug)]
+pub struct ReverseReader<R> {
+    reader: R,
+    position: u64,
+    buffer: Vec<u8>,
+    line: usize,
+    offset: FixedOffset,
+    continuation: Vec<(usize, u64, Vec<u8>)>,
+    queued: VecDeque<Result<Entry, ReadError>>,
+    done: bool,
+}
+
+impl ReverseReader<File> {
+    /// Open the logfile at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the file could not be opened.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        File::open(path).and_then(Self::new)
+    }
+}
+
+impl<R> ReverseReader<R>
+where
+    R: Read + Seek,
+{
+    /// Create a new reverse reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the end of the reader could not be determined.
+    pub fn new(mut reader: R) -> std::io::Result<Self> {
+        let position = reader.seek(SeekFrom::End(0))?;
+        Ok(Self {
+            reader,
+            position,
+            buffer: Vec::new(),
+            line: 0,
+            offset: Utc.fix(),
+            continuation: Vec::new(),
+            queued: VecDeque::new(),
+            done: false,
+        })
+    }
+
+    /// Assume the given offset for timestamps without a time zone.
+    #[must_use]
+    pub const fn with_default_offset(mut self, offset: FixedOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Return the `n` most recent transactions, from the newest to the oldest one.
+    ///
+    /// Unreadable entries are skipped.
+    #[must_use]
+    pub fn last_transactions(self, n: usize) -> Vec<Transaction> {
+        self.filter_map(Result::ok)
+            .reverse_transactions()
+            .take(n)
+            .collect()
+    }
+
+    /// Read the previous line and return its offset and bytes without its line terminator.
+    fn previous_line(&mut self) -> std::io::Result<Option<(u64, Vec<u8>)>> {
+        loop {
+            let end = if self.buffer.ends_with(b"\n") {
+                self.buffer.len() - 1
+            } else {
+                self.buffer.len()
+            };
+
+            if let Some(index) = self.buffer[..end].iter().rposition(|&byte| byte == b'\n') {
+                let line = trim_newline(&self.buffer[index + 1..]).to_vec();
+                self.buffer.truncate(index + 1);
+                return Ok(Some((self.position + index as u64 + 1, line)));
+            }
+
+            if self.position == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+
+                let line = trim_newline(&self.buffer).to_vec();
+                self.buffer.clear();
+                return Ok(Some((0, line)));
+            }
+
+            let size = BLOCK_SIZE.min(self.position);
+            self.position -= size;
+            self.reader.seek(SeekFrom::Start(self.position))?;
+            let mut block = vec![0; usize::try_from(size).unwrap_or(usize::MAX)];
+            self.reader.read_exact(&mut block)?;
+            block.append(&mut self.buffer);
+            self.buffer = block;
+        }
+    }
+
+    /// Queue errors for all continuation lines that could not be attached to an entry.
+    fn fail_continuation(&mut self) {
+        for (line, offset, bytes) in self.continuation.drain(..) {
+            let error = Error::MalformedEntry(String::from_utf8_lossy(&bytes).into_owned());
+            self.queued.push_back(Err(ReadError::new(
+                line,
+                offset,
+                bytes,
+                ReadErrorKind::Parse(error),
+            )));
+        }
+    }
+
+    fn process_line(&mut self, offset: u64, bytes: Vec<u8>) {
+        self.line += 1;
+        let text = String::from_utf8_lossy(&bytes);
+
+        match Entry::from_str_with_offset(&text, self.offset) {
+            Ok(mut entry) => {
+                let mut attached = 0;
+
+                for (_, _, line) in self.continuation.iter().rev() {
+                    if !entry.append_line(&String::from_utf8_lossy(line)) {
+                        break;
+                    }
+
+                    attached += 1;
+                }
+
+                self.continuation
+                    .truncate(self.continuation.len() - attached);
+                self.fail_continuation();
+                self.queued.push_back(Ok(entry));
+            }
+            Err(Error::MalformedEntry(_) | Error::InvalidTimestamp(_)) => {
+                self.continuation.push((self.line, offset, bytes));
+            }
+            Err(error) => {
+                self.fail_continuation();
+                self.queued.push_back(Err(ReadError::new(
+                    self.line,
+                    offset,
+                    bytes,
+                    ReadErrorKind::Parse(error),
+                )));
+            }
+        }
+    }
+}
+
+impl<R> Iterator for ReverseReader<R>
+where
+    R: Read + Seek,
+{
+    type Item = Result<Entry, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.queued.pop_front() {
+                return Some(item);
+            }
+
+            if self.done {
+                return None;
+            }
+
+            match self.previous_line() {
+                Ok(Some((offset, bytes))) => self.process_line(offset, bytes),
+                Ok(None) => {
+                    self.done = true;
+                    self.fail_continuation();
+                }
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(ReadError::new(
+                        self.line + 1,
+                        self.position,
+                        Vec::new(),
+                        ReadErrorKind::Io(error),
+                    )));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::ReverseReader;
+    use crate::fixture::LOG;
+    use crate::{Error, LogReader, ReadErrorKind, Transactions};
+
+    /// Return a log that spans several blocks.
+    fn log() -> String {
+        LOG.repeat(64)
+    }
+
+    #[test]
+    fn reads_the_same_entries_as_log_reader_backwards() {
+        let log = log();
+        let forward: Vec<_> = LogReader::new(log.as_bytes()).map(Result::unwrap).collect();
+        let mut backward: Vec<_> = ReverseReader::new(Cursor::new(&log))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        backward.reverse();
+        assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn reports_errors_with_lines_counted_from_the_end() {
+        let log = "\
+==> orphaned continuation line
+[2024-03-04T12:34:56+0100] [ALPM] transaction started
+[2024-03-04T12:34:57+0100] [ALPM] installed
+[2024-03-04T12:34:58+0100] [ALPM] transaction completed
+";
+        let items: Vec<_> = ReverseReader::new(Cursor::new(log)).unwrap().collect();
+        assert_eq!(items.len(), 4);
+        assert!(items[0].is_ok());
+
+        let error = items[1].as_ref().unwrap_err();
+        assert_eq!(error.line(), 2);
+        assert!(matches!(
+            error.kind(),
+            ReadErrorKind::Parse(Error::InvalidMessage(_))
+        ));
+
+        assert!(items[2].is_ok());
+        let error = items[3].as_ref().unwrap_err();
+        assert_eq!(error.line(), 4);
+        assert_eq!(error.offset(), 0);
+        assert!(matches!(
+            error.kind(),
+            ReadErrorKind::Parse(Error::MalformedEntry(_))
+        ));
+    }
+
+    #[test]
+    fn groups_the_same_transactions_as_forward_grouping() {
+        let log = log();
+        let forward: Vec<_> = LogReader::new(log.as_bytes())
+            .map(Result::unwrap)
+            .transactions()
+            .collect();
+        let mut backward: Vec<_> = ReverseReader::new(Cursor::new(&log))
+            .unwrap()
+            .map(Result::unwrap)
+            .reverse_transactions()
+            .collect();
+        backward.reverse();
+        assert_eq!(backward, forward);
+
+        let last = ReverseReader::new(Cursor::new(&log))
+            .unwrap()
+            .last_transactions(3);
+        assert!(last.iter().eq(forward.iter().rev().take(3)));
+    }
+}
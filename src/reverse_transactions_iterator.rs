@@ -0,0 +1,106 @@
+use std::mem::take;
+
+use crate::grouper::Grouper;
+use crate::{Entry, IntoEntry, Message, Transaction};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ReverseTransactionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    entries: T,
+    span: Vec<Entry>,
+    newer: Option<Vec<Entry>>,
+    done: bool,
+}
+
+impl<T> ReverseTransactionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    /// Group the newer segment into a transaction.
+    ///
+    /// The older segment precedes the newer one and provides the command,
+    /// full system upgrade and pre-transaction hook entries of the newer transaction.
+    /// Its package events are irrelevant to the newer transaction and thus skipped.
+    fn group(&mut self, older: &[Entry]) -> Option<Transaction> {
+        let newer = self.newer.take()?;
+        let mut grouper = Grouper::default();
+
+        for entry in older.iter().filter(|entry| {
+            !matches!(
+                entry.message(),
+                Message::Installed(_)
+                    | Message::Upgraded(_)
+                    | Message::Downgraded(_)
+                    | Message::Reinstalled(_)
+                    | Message::Removed(_)
+            )
+        }) {
+            grouper.push(entry.clone());
+        }
+
+        for entry in newer {
+            grouper.push(entry);
+        }
+
+        grouper.finish()
+    }
+}
+
+impl<T> From<T> for ReverseTransactionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    fn from(entries: T) -> Self {
+        Self {
+            entries,
+            span: Vec::new(),
+            newer: None,
+            done: false,
+        }
+    }
+}
+
+impl<T> Iterator for ReverseTransactionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    type Item = Transaction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let Some(entry) = self.entries.next() else {
+                self.done = true;
+                let mut prefix = take(&mut self.span);
+                prefix.reverse();
+                return self.group(&prefix);
+            };
+
+            if !entry.is_groupable() {
+                continue;
+            }
+
+            let entry = entry.into();
+            let is_start = matches!(entry.message(), Message::TransactionStarted);
+            self.span.push(entry);
+
+            if is_start {
+                let mut segment = take(&mut self.span);
+                segment.reverse();
+                let transaction = self.group(&segment);
+                self.newer.replace(segment);
+
+                if transaction.is_some() {
+                    return transaction;
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::{HashSet, VecDeque};
+use std::ffi::OsStr;
+use std::io::{BufRead, Read};
+use std::path::{Path, PathBuf};
+
+use crate::decompress;
+
+/// Amount of trailing lines of a file to compare against the start of the next file.
+const OVERLAP_WINDOW: usize = 1024;
+const COMPRESSION_EXTENSIONS: [&str; 4] = [".gz", ".xz", ".zst", ".bz2"];
+
+/// Reads a logfile and its rotated siblings as one continuous stream of lines.
+///
+/// Files are read from oldest to newest, i.e. `pacman.log.2.gz`, `pacman.log.1`, `pacman.log`.
+/// Compressed files are detected by their magic bytes.
+/// Lines at the start of a file that duplicate the end of the previous file are skipped.
+pub struct RotatedReader {
+    paths: VecDeque<PathBuf>,
+    current: Option<Box<dyn BufRead>>,
+    previous: HashSet<Vec<u8>>,
+    tail: VecDeque<Vec<u8>>,
+    skipping: bool,
+    line: Vec<u8>,
+    position: usize,
+}
+
+impl RotatedReader {
+    /// Find all rotated siblings of the logfile at the given base path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the parent directory could not be read.
+    pub fn open(base: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::from_paths(rotated_paths(base.as_ref())?))
+    }
+
+    /// Read the given files in order.
+    #[must_use]
+    pub fn from_paths(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            paths: paths.into_iter().collect(),
+            current: None,
+            previous: HashSet::new(),
+            tail: VecDeque::new(),
+            skipping: false,
+            line: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Return the files that have yet to be read.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.paths.iter().map(PathBuf::as_path)
+    }
+
+    fn next_line(&mut self) -> std::io::Result<bool> {
+        loop {
+            if self.current.is_none() {
+                let Some(path) = self.paths.pop_front() else {
+                    return Ok(false);
+                };
+
+                // Keep comparing against the last lines read if the previous file was empty.
+                if !self.tail.is_empty() {
+                    self.previous = self.tail.drain(..).collect();
+                }
+
+                self.skipping = !self.previous.is_empty();
+                self.current = Some(decompress::open(&path)?);
+            }
+
+            let Some(reader) = self.current.as_mut() else {
+                return Ok(false);
+            };
+
+            self.line.clear();
+            self.position = 0;
+
+            if reader.read_until(b'\n', &mut self.line)? == 0 {
+                self.current = None;
+                continue;
+            }
+
+            if !self.line.ends_with(b"\n") {
+                self.line.push(b'\n');
+            }
+
+            let key = &self.line[..self.line.len() - 1];
+
+            if self.tail.len() >= OVERLAP_WINDOW {
+                self.tail.pop_front();
+            }
+
+            self.tail.push_back(key.to_vec());
+
+            if self.skipping && self.previous.contains(key) {
+                continue;
+            }
+
+            self.skipping = false;
+            return Ok(true);
+        }
+    }
+}
+
+impl Read for RotatedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let size = available.len().min(buf.len());
+        buf[..size].copy_from_slice(&available[..size]);
+        self.consume(size);
+        Ok(size)
+    }
+}
+
+impl BufRead for RotatedReader {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.position >= self.line.len() && !self.next_line()? {
+            return Ok(&[]);
+        }
+
+        Ok(&self.line[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.line.len());
+    }
+}
+
+/// Position of a file within the rotation, ordered from oldest to newest.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Rotation {
+    Dated(String),
+    Numbered(std::cmp::Reverse<u64>),
+    Current,
+}
+
+/// Return the base path and its rotated siblings, ordered from oldest to newest.
+fn rotated_paths(base: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let Some(name) = base.file_name().and_then(OsStr::to_str) else {
+        return Ok(vec![base.to_path_buf()]);
+    };
+    let directory = base
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let mut paths = Vec::new();
+
+    for entry in directory.read_dir()? {
+        let path = entry?.path();
+
+        if let Some(rotation) = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .and_then(|file_name| file_name.strip_prefix(name))
+            .and_then(rotation)
+        {
+            paths.push((rotation, path));
+        }
+    }
+
+    paths.sort();
+    Ok(paths.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Return the position of a file with the given suffix within the rotation.
+///
+/// Only numbered (`.1`) and dated (`-20240301`) suffixes, optionally followed by a compression extension,
+/// are part of the rotation, so that backups like `pacman.log-old.bak` are ignored.
+fn rotation(suffix: &str) -> Option<Rotation> {
+    let suffix = COMPRESSION_EXTENSIONS
+        .iter()
+        .find_map(|extension| suffix.strip_suffix(extension))
+        .unwrap_or(suffix);
+
+    if suffix.is_empty() {
+        return Some(Rotation::Current);
+    }
+
+    if let Some(number) = suffix.strip_prefix('.').and_then(digits) {
+        return number
+            .parse()
+            .ok()
+            .map(|number| Rotation::Numbered(std::cmp::Reverse(number)));
+    }
+
+    suffix
+        .strip_prefix('-')
+        .and_then(digits)
+        .map(|date| Rotation::Dated(date.to_string()))
+}
+
+/// Return the text if it consists of ASCII digits only.
+fn digits(text: &str) -> Option<&str> {
+    (!text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit())).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::ops::Range;
+
+    use super::RotatedReader;
+    use crate::fixture::{TempDir, LOG};
+
+    /// Return the given lines of the log.
+    fn lines(range: Range<usize>) -> String {
+        LOG.lines()
+            .skip(range.start)
+            .take(range.len())
+            .fold(String::new(), |mut lines, line| {
+                lines.push_str(line);
+                lines.push('\n');
+                lines
+            })
+    }
+
+    fn read(reader: &mut RotatedReader) -> String {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).unwrap();
+        text
+    }
+
+    #[test]
+    fn ignores_files_outside_of_the_rotation() {
+        let dir = TempDir::new("rotated-stray");
+        dir.write("pacman.log.1", lines(0..20));
+        dir.write(
+            "pacman.log-old.bak",
+            "[2020-01-01T00:00:00+0100] [ALPM] stray\n",
+        );
+        dir.write("pacman.log.idx", "pacmanlog-index 2\n");
+        let base = dir.write("pacman.log", lines(20..usize::MAX));
+
+        let mut reader = RotatedReader::open(&base).unwrap();
+        assert_eq!(
+            reader
+                .paths()
+                .map(|path| path.file_name().unwrap().to_str().unwrap())
+                .collect::<Vec<_>>(),
+            ["pacman.log.1", "pacman.log"]
+        );
+        assert_eq!(read(&mut reader), LOG);
+    }
+
+    #[test]
+    fn reads_numbered_files_from_oldest_to_newest() {
+        let dir = TempDir::new("rotated-numbered");
+        dir.write("pacman.log.10", lines(0..10));
+        dir.write("pacman.log.2", lines(10..20));
+        dir.write("pacman.log.1", lines(20..30));
+        let base = dir.write("pacman.log", lines(30..usize::MAX));
+
+        assert_eq!(read(&mut RotatedReader::open(base).unwrap()), LOG);
+    }
+
+    #[test]
+    fn reads_dated_files_from_oldest_to_newest() {
+        let dir = TempDir::new("rotated-dated");
+        dir.write("pacman.log-20240302", lines(15..30));
+        dir.write("pacman.log-20240301", lines(0..15));
+        let base = dir.write("pacman.log", lines(30..usize::MAX));
+
+        assert_eq!(read(&mut RotatedReader::open(base).unwrap()), LOG);
+    }
+
+    #[test]
+    fn skips_lines_duplicated_from_the_previous_file() {
+        let dir = TempDir::new("rotated-overlap");
+        dir.write("pacman.log.2", lines(0..20));
+        dir.write("pacman.log.1", lines(10..20));
+        let base = dir.write("pacman.log", lines(15..usize::MAX));
+
+        assert_eq!(read(&mut RotatedReader::open(base).unwrap()), LOG);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn decompresses_rotated_files() {
+        use std::io::Write;
+
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(lines(0..20).as_bytes()).unwrap();
+        let dir = TempDir::new("rotated-gzip");
+        dir.write("pacman.log.1.gz", encoder.finish().unwrap());
+        let base = dir.write("pacman.log", lines(20..usize::MAX));
+
+        assert_eq!(read(&mut RotatedReader::open(base).unwrap()), LOG);
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::{Command, Entry, Message, Transaction};
+
+/// Representation of a single pacman invocation and the transactions it performed.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Session {
+    command: Option<Entry>,
+    sync: Option<Entry>,
+    full_system_upgrade: Option<Entry>,
+    transactions: Vec<Transaction>,
+    problems: Vec<Entry>,
+}
+
+impl Session {
+    /// Create a new session from the entry of the command that invoked it, if any.
+    pub(crate) const fn new(command: Option<Entry>) -> Self {
+        Self {
+            command,
+            sync: None,
+            full_system_upgrade: None,
+            transactions: Vec::new(),
+            problems: Vec::new(),
+        }
+    }
+
+    /// Return the entry of the command that invoked this session.
+    #[must_use]
+    pub const fn command_entry(&self) -> Option<&Entry> {
+        self.command.as_ref()
+    }
+
+    /// Return the command that invoked this session.
+    #[must_use]
+    pub fn command(&self) -> Option<&Command> {
+        self.command
+            .as_ref()
+            .and_then(|entry| match entry.message() {
+                Message::Running(command) => Some(command),
+                _ => None,
+            })
+    }
+
+    /// Return the entry that announced the synchronization of the package databases.
+    #[must_use]
+    pub const fn sync(&self) -> Option<&Entry> {
+        self.sync.as_ref()
+    }
+
+    /// Return whether the package databases were synchronized in this session.
+    #[must_use]
+    pub const fn is_synchronized(&self) -> bool {
+        self.sync.is_some()
+    }
+
+    /// Return the entry that announced a full system upgrade.
+    #[must_use]
+    pub const fn full_system_upgrade(&self) -> Option<&Entry> {
+        self.full_system_upgrade.as_ref()
+    }
+
+    /// Return whether this session was a full system upgrade.
+    #[must_use]
+    pub const fn is_full_system_upgrade(&self) -> bool {
+        self.full_system_upgrade.is_some()
+    }
+
+    /// Return whether packages were changed in this session after the package databases
+    /// have been synchronized without a full system upgrade, possibly in an earlier session.
+    ///
+    /// See [`Transaction::is_sync_without_upgrade`] for details.
+    #[must_use]
+    pub fn is_sync_without_upgrade(&self) -> bool {
+        self.transactions
+            .iter()
+            .any(Transaction::is_sync_without_upgrade)
+    }
+
+    /// Return the transactions performed in this session.
+    #[must_use]
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    /// Return the warning and error entries logged during this session.
+    ///
+    /// Unlike [`Transaction::problems`], this includes those of sessions that did not start a transaction,
+    /// e.g. because of file conflicts.
+    #[must_use]
+    pub fn problems(&self) -> &[Entry] {
+        &self.problems
+    }
+
+    /// Return the time of the first entry of this session.
+    #[must_use]
+    pub fn begin(&self) -> Option<DateTime<FixedOffset>> {
+        self.command
+            .iter()
+            .chain(&self.sync)
+            .chain(&self.full_system_upgrade)
+            .map(Entry::timestamp)
+            .chain(self.transactions.first().map(Transaction::begin))
+            .min()
+    }
+
+    /// Return the time at which the last transaction was completed or interrupted.
+    #[must_use]
+    pub fn end(&self) -> Option<DateTime<FixedOffset>> {
+        self.transactions.last().and_then(Transaction::end)
+    }
+
+    /// Set the entry that announced the synchronization of the package databases.
+    pub(crate) fn set_sync(&mut self, entry: Entry) {
+        self.sync.replace(entry);
+    }
+
+    /// Set the entry that announced a full system upgrade.
+    pub(crate) fn set_full_system_upgrade(&mut self, entry: Entry) {
+        self.full_system_upgrade.replace(entry);
+    }
+
+    /// Add a warning or error entry logged during this session.
+    pub(crate) fn push_problem(&mut self, entry: Entry) {
+        self.problems.push(entry);
+    }
+
+    /// Add a transaction performed in this session.
+    pub(crate) fn push(&mut self, transaction: Transaction) {
+        self.transactions.push(transaction);
+    }
+}
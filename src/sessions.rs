@@ -0,0 +1,24 @@
+use crate::{IntoEntry, SessionsIterator};
+
+/// Group entries into pacman invocations.
+pub trait Sessions: Iterator + Sized
+where
+    Self::Item: IntoEntry,
+{
+    /// Return an iterator of [`Session`](crate::Session)s.
+    ///
+    /// Each `Running` entry starts a new session, which comprises the database synchronization,
+    /// full system upgrade announcement and transactions that follow it.
+    /// Transactions logged before the first `Running` entry form sessions without a command.
+    fn sessions(self) -> SessionsIterator<Self>;
+}
+
+impl<T> Sessions for T
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    fn sessions(self) -> SessionsIterator<Self> {
+        SessionsIterator::from(self)
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::VecDeque;
+
+use crate::grouper::Grouper;
+use crate::{IntoEntry, Message, Session, Transaction};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    entries: T,
+    grouper: Grouper,
+    sessions: VecDeque<Session>,
+    first: usize,
+    owners: VecDeque<usize>,
+    started: bool,
+    done: bool,
+}
+
+impl<T> SessionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    /// Return the number of the session that subsequent entries belong to.
+    ///
+    /// A new session without a command is started if there is none yet
+    /// or if the latest session has no command and already started a transaction.
+    fn current(&mut self) -> usize {
+        let is_new = self
+            .sessions
+            .back()
+            .is_none_or(|session| session.command_entry().is_none() && self.started);
+
+        if is_new {
+            self.sessions.push_back(Session::new(None));
+            self.started = false;
+        }
+
+        self.first + self.sessions.len() - 1
+    }
+
+    /// Return the number of the latest session, starting one without a command if there is none yet.
+    fn latest(&mut self) -> usize {
+        if self.sessions.is_empty() {
+            self.sessions.push_back(Session::new(None));
+            self.started = false;
+        }
+
+        self.first + self.sessions.len() - 1
+    }
+
+    /// Attach the transaction to the session in which it was started.
+    fn attach(&mut self, transaction: Transaction) {
+        if let Some(session) = self
+            .owners
+            .pop_front()
+            .and_then(|owner| self.sessions.get_mut(owner - self.first))
+        {
+            session.push(transaction);
+        }
+    }
+
+    /// Return the oldest session if it cannot receive any further transactions.
+    ///
+    /// The latest session is held back until the entries are exhausted.
+    fn pop_complete(&mut self) -> Option<Session> {
+        let is_complete = if self.done {
+            !self.sessions.is_empty()
+        } else {
+            self.sessions.len() > 1 && self.owners.front().is_none_or(|&owner| owner > self.first)
+        };
+
+        if is_complete {
+            self.first += 1;
+            self.sessions.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> From<T> for SessionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    fn from(entries: T) -> Self {
+        Self {
+            entries,
+            grouper: Grouper::default(),
+            sessions: VecDeque::new(),
+            first: 0,
+            owners: VecDeque::new(),
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl<T> Iterator for SessionsIterator<T>
+where
+    T: Iterator,
+    T::Item: IntoEntry,
+{
+    type Item = Session;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(session) = self.pop_complete() {
+                return Some(session);
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let Some(entry) = self.entries.next() else {
+                self.done = true;
+
+                if let Some(transaction) = self.grouper.finish() {
+                    self.attach(transaction);
+                }
+
+                continue;
+            };
+
+            if !entry.is_groupable() {
+                continue;
+            }
+
+            let entry = entry.into();
+
+            match entry.message() {
+                Message::Running(_) => {
+                    self.sessions.push_back(Session::new(Some(entry.clone())));
+                    self.started = false;
+                }
+                Message::SynchronizingPackageLists => {
+                    let current = self.current() - self.first;
+                    self.sessions[current].set_sync(entry.clone());
+                }
+                Message::StartingFullSystemUpgrade => {
+                    let current = self.current() - self.first;
+                    self.sessions[current].set_full_system_upgrade(entry.clone());
+                }
+                Message::TransactionStarted => {
+                    let current = self.current();
+                    self.owners.push_back(current);
+                    self.started = true;
+                }
+                Message::Warning(_) | Message::Error(_) => {
+                    let latest = self.latest() - self.first;
+                    self.sessions[latest].push_problem(entry.clone());
+                }
+                _ => {}
+            }
+
+            if let Some(transaction) = self.grouper.push(entry) {
+                self.attach(transaction);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::LOG;
+    use crate::{LogReader, Message, Session, Sessions, Transactions};
+
+    #[test]
+    fn groups_transactions_into_invocations() {
+        let sessions: Vec<_> = LogReader::new(LOG.as_bytes())
+            .map(Result::unwrap)
+            .sessions()
+            .collect();
+        let commands: Vec<_> = sessions
+            .iter()
+            .map(|session| {
+                let command = session.command().unwrap();
+                (command.targets().join(" "), session.transactions().len())
+            })
+            .collect();
+        assert_eq!(
+            commands,
+            [
+                (String::new(), 1),
+                ("linux".to_string(), 0),
+                ("firefox".to_string(), 1),
+                ("firefox".to_string(), 1),
+                ("vim".to_string(), 1),
+                ("python".to_string(), 1),
+            ]
+        );
+
+        assert!(sessions[0].is_synchronized());
+        assert!(sessions[0].is_full_system_upgrade());
+        assert!(sessions[5].is_synchronized());
+        assert!(!sessions[5].is_full_system_upgrade());
+        assert!(sessions[5].is_sync_without_upgrade());
+        assert!(sessions[..5]
+            .iter()
+            .all(|session| !session.is_sync_without_upgrade()));
+
+        assert!(matches!(
+            sessions[1].problems(),
+            [entry] if matches!(entry.message(), Message::Error(_))
+        ));
+        assert!(matches!(
+            sessions[3].problems(),
+            [entry] if matches!(entry.message(), Message::Warning(_))
+        ));
+
+        assert!(sessions
+            .iter()
+            .flat_map(Session::transactions)
+            .eq(&LogReader::new(LOG.as_bytes())
+                .map(Result::unwrap)
+                .transactions()
+                .collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn starts_session_without_command_for_legacy_entries() {
+        let log = "\
+[2014-03-04 12:00] starting full system upgrade
+[2014-03-04 12:01] upgraded linux (3.13.5-1 -> 3.13.6-1)
+[2014-03-05 12:00] installed vim (7.4.193-1)
+";
+        let sessions: Vec<_> = LogReader::new(log.as_bytes())
+            .map(Result::unwrap)
+            .sessions()
+            .collect();
+        assert_eq!(sessions.len(), 1);
+        assert!(sessions[0].command().is_none());
+        assert!(sessions[0].is_full_system_upgrade());
+        assert!(sessions[0].transactions().is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+
+/// Split a log line into its timestamp, optional issuer and message.
+///
+/// Matches `[timestamp] [issuer] message` and `[timestamp] message`.
+pub fn entry(line: &str) -> Option<(&str, Option<&str>, &str)> {
+    let (timestamp, rest) = line.strip_prefix('[')?.split_once("] ")?;
+
+    if timestamp.is_empty() || timestamp.contains(']') {
+        return None;
+    }
+
+    let (issuer, message) = rest
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .filter(|(issuer, _)| !issuer.is_empty() && !issuer.contains(']'))
+        .map_or((None, rest), |(issuer, message)| (Some(issuer), message));
+
+    (!message.is_empty()).then_some((timestamp, issuer, message))
+}
+
+/// Split `name (version)` into name and version.
+pub fn package(text: &str) -> Option<(&str, &str)> {
+    let (name, version) = text.strip_suffix(')')?.rsplit_once(" (")?;
+    (!name.is_empty() && !version.is_empty()).then_some((name, version))
+}
+
+/// Split `name (old -> new)` into name, old version and new version.
+pub fn version_change(text: &str) -> Option<(&str, &str, &str)> {
+    let (name, versions) = package(text)?;
+    let (old_version, new_version) = versions.rsplit_once(" -> ")?;
+    (!old_version.is_empty() && !new_version.is_empty()).then_some((name, old_version, new_version))
+}
+
+/// Parse a timestamp of the form `2024-03-04T12:34:56+0100`.
+///
+/// This is a fast path for the timestamps written by current pacman versions.
+/// Returns `None` if the timestamp has any other shape.
+pub fn timestamp(text: &str) -> Option<DateTime<FixedOffset>> {
+    let bytes = text.as_bytes();
+
+    if bytes.len() != 24
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || bytes[10] != b'T'
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+
+    let sign = match bytes[19] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let date = NaiveDate::from_ymd_opt(
+        number(&bytes[0..4])?.try_into().ok()?,
+        number(&bytes[5..7])?,
+        number(&bytes[8..10])?,
+    )?;
+    let time = NaiveTime::from_hms_opt(
+        number(&bytes[11..13])?,
+        number(&bytes[14..16])?,
+        number(&bytes[17..19])?,
+    )?;
+    let seconds = number(&bytes[20..22])? * 3600 + number(&bytes[22..24])? * 60;
+    let offset = FixedOffset::east_opt(sign * i32::try_from(seconds).ok()?)?;
+    date.and_time(time).and_local_timezone(offset).single()
+}
+
+/// Parse a fixed-width decimal number.
+fn number(digits: &[u8]) -> Option<u32> {
+    digits.iter().try_fold(0, |number, digit| {
+        digit
+            .is_ascii_digit()
+            .then(|| number * 10 + u32::from(digit - b'0'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, TimeZone};
+
+    use super::{entry, package, timestamp, version_change};
+
+    #[test]
+    fn splits_entry_with_issuer() {
+        assert_eq!(
+            entry("[2024-03-04T12:34:56+0100] [ALPM] installed linux (6.7.8.arch1-1)"),
+            Some((
+                "2024-03-04T12:34:56+0100",
+                Some("ALPM"),
+                "installed linux (6.7.8.arch1-1)"
+            ))
+        );
+    }
+
+    #[test]
+    fn splits_entry_without_issuer() {
+        assert_eq!(
+            entry("[2011-05-16 21:39] upgraded pacman (3.5.1-1 -> 3.5.2-1)"),
+            Some((
+                "2011-05-16 21:39",
+                None,
+                "upgraded pacman (3.5.1-1 -> 3.5.2-1)"
+            ))
+        );
+    }
+
+    #[test]
+    fn keeps_brackets_within_message() {
+        assert_eq!(
+            entry("[2024-03-04T12:34:56+0100] [ALPM-SCRIPTLET] [ OK ] done"),
+            Some((
+                "2024-03-04T12:34:56+0100",
+                Some("ALPM-SCRIPTLET"),
+                "[ OK ] done"
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert_eq!(entry("==> Building initcpio image"), None);
+        assert_eq!(entry("[] [ALPM] transaction started"), None);
+        assert_eq!(entry("[2024-03-04T12:34:56+0100] "), None);
+        assert_eq!(entry("[2024-03-04T12:34:56+0100]"), None);
+    }
+
+    #[test]
+    fn splits_package() {
+        assert_eq!(
+            package("linux-firmware (20240220.97b693d2-1)"),
+            Some(("linux-firmware", "20240220.97b693d2-1"))
+        );
+        assert_eq!(package("linux"), None);
+        assert_eq!(package(" (1.0-1)"), None);
+    }
+
+    #[test]
+    fn splits_version_change() {
+        assert_eq!(
+            version_change("glibc (2.39-1 -> 2.39+r6+g9b5e1ee1a8-1)"),
+            Some(("glibc", "2.39-1", "2.39+r6+g9b5e1ee1a8-1"))
+        );
+        assert_eq!(version_change("glibc (2.39-1)"), None);
+    }
+
+    #[test]
+    fn parses_timestamp() {
+        assert_eq!(
+            timestamp("2024-03-04T12:34:56+0100"),
+            FixedOffset::east_opt(3600)
+                .and_then(|offset| offset.with_ymd_and_hms(2024, 3, 4, 12, 34, 56).single())
+        );
+        assert_eq!(
+            timestamp("2024-03-04T12:34:56-0530"),
+            FixedOffset::west_opt(5 * 3600 + 30 * 60)
+                .and_then(|offset| offset.with_ymd_and_hms(2024, 3, 4, 12, 34, 56).single())
+        );
+    }
+
+    #[test]
+    fn rejects_other_timestamps() {
+        assert_eq!(timestamp("2016-03-04 12:34"), None);
+        assert_eq!(timestamp("2024-03-04T12:34:56+01:00"), None);
+        assert_eq!(timestamp("2024-13-04T12:34:56+0100"), None);
+        assert_eq!(timestamp("2024-03-04T12:3a:56+0100"), None);
+    }
+}
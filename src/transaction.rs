@@ -1,45 +1,109 @@
 use chrono::{DateTime, FixedOffset};
 
+pub use outcome::Outcome;
+pub use package_event::PackageEvent;
+
 use crate::message::Package;
-use crate::{Entry, Upgrade};
+use crate::{Command, ConfigFile, Downgrade, Entry, Hook, HookPhase, Message, Upgrade};
+
+mod outcome;
+mod package_event;
 
 /// Representation of a pacman transaction.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Transaction {
-    start: Entry,
-    installed: Box<[Package]>,
-    upgraded: Box<[Upgrade]>,
-    reinstalled: Box<[Package]>,
-    removed: Box<[Package]>,
-    completion: Option<Entry>,
-    hooks: Box<[Entry]>,
+    pub(crate) command: Option<Entry>,
+    pub(crate) sync: Option<Entry>,
+    pub(crate) last_sync: Option<Entry>,
+    pub(crate) full_system_upgrade: Option<Entry>,
+    pub(crate) start: Entry,
+    pub(crate) installed: Box<[PackageEvent<Package>]>,
+    pub(crate) upgraded: Box<[PackageEvent<Upgrade>]>,
+    pub(crate) downgraded: Box<[PackageEvent<Downgrade>]>,
+    pub(crate) reinstalled: Box<[PackageEvent<Package>]>,
+    pub(crate) removed: Box<[PackageEvent<Package>]>,
+    pub(crate) completion: Option<Entry>,
+    pub(crate) interruption: Option<Entry>,
+    pub(crate) hooks: Box<[Hook]>,
+    pub(crate) pacnew: Box<[ConfigFile]>,
+    pub(crate) pacsave: Box<[ConfigFile]>,
+    pub(crate) problems: Box<[Entry]>,
+    pub(crate) other: Box<[Entry]>,
 }
 
 impl Transaction {
-    /// Create a new transaction.
+    /// Return the entry of the command that invoked this transaction.
+    #[must_use]
+    pub const fn command_entry(&self) -> Option<&Entry> {
+        self.command.as_ref()
+    }
+
+    /// Return the command that invoked this transaction.
+    #[must_use]
+    pub fn command(&self) -> Option<&Command> {
+        self.command
+            .as_ref()
+            .and_then(|entry| match entry.message() {
+                Message::Running(command) => Some(command),
+                _ => None,
+            })
+    }
+
+    /// Return the entry that announced the synchronization of the package databases.
+    #[must_use]
+    pub const fn sync(&self) -> Option<&Entry> {
+        self.sync.as_ref()
+    }
+
+    /// Return whether the package databases were synchronized before this transaction.
+    #[must_use]
+    pub const fn is_synchronized(&self) -> bool {
+        self.sync.is_some()
+    }
+
+    /// Return the entry of the most recent synchronization of the package databases
+    /// that has not been followed by a full system upgrade before this transaction.
     ///
-    /// This method is crate-only since we do not want users to
-    /// create new transactions from arbitrary entries.
-    #[must_use]
-
-    pub(crate) const fn new(
-        start: Entry,
-        installed: Box<[Package]>,
-        upgraded: Box<[Upgrade]>,
-        reinstalled: Box<[Package]>,
-        removed: Box<[Package]>,
-        completion: Option<Entry>,
-        hooks: Box<[Entry]>,
-    ) -> Self {
-        Self {
-            start,
-            installed,
-            upgraded,
-            reinstalled,
-            removed,
-            completion,
-            hooks,
-        }
+    /// Unlike [`Transaction::sync`], this may stem from an earlier invocation of pacman,
+    /// e.g. `pacman -Sy` followed by `pacman -S <package>`.
+    /// Transactions grouped by [`Transactions::reverse_transactions`](crate::Transactions::reverse_transactions)
+    /// only consider synchronizations since the preceding transaction
+    /// and those read from an [`Index`](crate::Index) only those of their own invocation.
+    #[must_use]
+    pub const fn last_sync(&self) -> Option<&Entry> {
+        self.last_sync.as_ref()
+    }
+
+    /// Return whether this transaction changed packages after the package databases
+    /// have been synchronized without a full system upgrade.
+    ///
+    /// This is the case for `pacman -Sy <package>` or `pacman -Sy` followed by `pacman -S <package>`,
+    /// which leave the system partially upgraded.
+    #[must_use]
+    pub const fn is_sync_without_upgrade(&self) -> bool {
+        self.last_sync.is_some()
+            && (!self.installed.is_empty()
+                || !self.upgraded.is_empty()
+                || !self.downgraded.is_empty()
+                || !self.reinstalled.is_empty())
+    }
+
+    /// Return the entry that announced a full system upgrade.
+    #[must_use]
+    pub const fn full_system_upgrade(&self) -> Option<&Entry> {
+        self.full_system_upgrade.as_ref()
+    }
+
+    /// Return whether this transaction was part of a full system upgrade.
+    #[must_use]
+    pub const fn is_full_system_upgrade(&self) -> bool {
+        self.full_system_upgrade.is_some()
+    }
+
+    /// Return whether this transaction upgraded packages without a full system upgrade.
+    #[must_use]
+    pub const fn is_partial_upgrade(&self) -> bool {
+        !self.upgraded.is_empty() && !self.is_full_system_upgrade()
     }
 
     /// Return the start entry.
@@ -50,25 +114,31 @@ impl Transaction {
 
     /// Return a slice of packets that were installed in this transaction.
     #[must_use]
-    pub const fn installed(&self) -> &[Package] {
+    pub const fn installed(&self) -> &[PackageEvent<Package>] {
         &self.installed
     }
 
     /// Return a slice of packets that were upgraded in this transaction.
     #[must_use]
-    pub const fn upgraded(&self) -> &[Upgrade] {
+    pub const fn upgraded(&self) -> &[PackageEvent<Upgrade>] {
         &self.upgraded
     }
 
+    /// Return a slice of packets that were downgraded in this transaction.
+    #[must_use]
+    pub const fn downgraded(&self) -> &[PackageEvent<Downgrade>] {
+        &self.downgraded
+    }
+
     /// Return a slice of packets that were reinstalled in this transaction.
     #[must_use]
-    pub const fn reinstalled(&self) -> &[Package] {
+    pub const fn reinstalled(&self) -> &[PackageEvent<Package>] {
         &self.reinstalled
     }
 
     /// Return a slice of packets that were removed in this transaction.
     #[must_use]
-    pub const fn removed(&self) -> &[Package] {
+    pub const fn removed(&self) -> &[PackageEvent<Package>] {
         &self.removed
     }
 
@@ -78,16 +148,117 @@ impl Transaction {
         self.completion.as_ref()
     }
 
+    /// Return the interruption entry.
+    #[must_use]
+    pub const fn interruption(&self) -> Option<&Entry> {
+        self.interruption.as_ref()
+    }
+
+    /// Return how the transaction ended.
+    #[must_use]
+    pub const fn outcome(&self) -> Outcome {
+        if self.completion.is_some() {
+            Outcome::Completed
+        } else if self.interruption.is_some() {
+            Outcome::Interrupted
+        } else {
+            Outcome::Incomplete
+        }
+    }
+
+    /// Return the hooks that ran as part of this transaction.
+    ///
+    /// Pre-transaction hooks are told apart from post-transaction hooks of the preceding transaction
+    /// by the `Running` entry of the invocation. In logs without such entries,
+    /// pre-transaction hooks are attributed to the preceding transaction as post-transaction hooks.
+    #[must_use]
+    pub const fn hooks(&self) -> &[Hook] {
+        &self.hooks
+    }
+
+    /// Return the hooks that ran before this transaction was started.
+    pub fn pre_transaction_hooks(&self) -> impl Iterator<Item = &Hook> {
+        self.hooks
+            .iter()
+            .filter(|hook| hook.phase() == HookPhase::PreTransaction)
+    }
+
+    /// Return the hooks that ran after this transaction was started.
+    pub fn post_transaction_hooks(&self) -> impl Iterator<Item = &Hook> {
+        self.hooks
+            .iter()
+            .filter(|hook| hook.phase() == HookPhase::PostTransaction)
+    }
+
+    /// Return the configuration files that have been installed as `.pacnew` in this transaction.
+    #[must_use]
+    pub const fn pacnew_files(&self) -> &[ConfigFile] {
+        &self.pacnew
+    }
+
+    /// Return the configuration files that have been saved as `.pacsave` in this transaction.
+    #[must_use]
+    pub const fn pacsave_files(&self) -> &[ConfigFile] {
+        &self.pacsave
+    }
+
+    /// Return the warning and error entries logged during or after this transaction.
+    #[must_use]
+    pub const fn problems(&self) -> &[Entry] {
+        &self.problems
+    }
+
+    /// Return the warnings logged during or after this transaction.
+    pub fn warnings(&self) -> impl Iterator<Item = &str> {
+        self.problems
+            .iter()
+            .filter_map(|entry| match entry.message() {
+                Message::Warning(text) => Some(text.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Return the errors logged during or after this transaction.
+    pub fn errors(&self) -> impl Iterator<Item = &str> {
+        self.problems
+            .iter()
+            .filter_map(|entry| match entry.message() {
+                Message::Error(text) => Some(text.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Return the entries logged during or after this transaction
+    /// that are neither package events, hooks nor scriptlet output.
+    #[must_use]
+    pub const fn other(&self) -> &[Entry] {
+        &self.other
+    }
+
     /// Return the start time.
     #[must_use]
     pub const fn begin(&self) -> DateTime<FixedOffset> {
         self.start.timestamp()
     }
 
-    /// Return the end time.
+    /// Return the time at which the package databases were synchronized.
+    #[must_use]
+    pub fn sync_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.sync().map(Entry::timestamp)
+    }
+
+    /// Return the time at which the full system upgrade was started.
+    #[must_use]
+    pub fn full_system_upgrade_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.full_system_upgrade().map(Entry::timestamp)
+    }
+
+    /// Return the time at which the transaction was completed or interrupted.
     #[must_use]
     pub fn end(&self) -> Option<DateTime<FixedOffset>> {
-        self.completion().map(Entry::timestamp)
+        self.completion()
+            .or_else(|| self.interruption())
+            .map(Entry::timestamp)
     }
 
     /// Return an iterator of all packages that were part of this transaction.
@@ -96,8 +267,9 @@ impl Transaction {
             .iter()
             .chain(self.reinstalled.iter())
             .chain(self.removed.iter())
-            .map(Package::name)
-            .chain(self.upgraded.iter().map(Upgrade::name))
+            .map(|event| event.name())
+            .chain(self.upgraded.iter().map(|event| event.name()))
+            .chain(self.downgraded.iter().map(|event| event.name()))
     }
 
     /// Return an iterator of all packages that were retained in this transaction.
@@ -105,7 +277,24 @@ impl Transaction {
         self.installed
             .iter()
             .chain(self.reinstalled.iter())
-            .map(Package::name)
-            .chain(self.upgraded.iter().map(Upgrade::name))
+            .map(|event| event.name())
+            .chain(self.upgraded.iter().map(|event| event.name()))
+            .chain(self.downgraded.iter().map(|event| event.name()))
+    }
+
+    /// Return the amount of packages that were part of this transaction.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.installed.len()
+            + self.upgraded.len()
+            + self.downgraded.len()
+            + self.reinstalled.len()
+            + self.removed.len()
+    }
+
+    /// Return whether no packages were part of this transaction.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
@@ -0,0 +1,10 @@
+/// The way a transaction ended.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Outcome {
+    /// The transaction has been completed.
+    Completed,
+    /// The transaction has been interrupted.
+    Interrupted,
+    /// The next transaction started or the log ended before the transaction was completed or interrupted.
+    Incomplete,
+}
@@ -0,0 +1,67 @@
+use std::ops::Deref;
+
+use crate::{Entry, Message};
+
+/// A package operation of a transaction along with the output of its install scriptlet.
+///
+/// Dereferences to the underlying [`Package`](crate::Package), [`Upgrade`](crate::Upgrade)
+/// or [`Downgrade`](crate::Downgrade).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PackageEvent<T> {
+    package: T,
+    scriptlet_output: Vec<Entry>,
+}
+
+impl<T> PackageEvent<T> {
+    /// Create a new package event without scriptlet output.
+    pub(crate) const fn new(package: T) -> Self {
+        Self {
+            package,
+            scriptlet_output: Vec::new(),
+        }
+    }
+
+    /// Return the package operation.
+    #[must_use]
+    pub const fn package(&self) -> &T {
+        &self.package
+    }
+
+    /// Return the scriptlet output entries logged right after the package operation.
+    #[must_use]
+    pub fn scriptlet_output(&self) -> &[Entry] {
+        &self.scriptlet_output
+    }
+
+    /// Return the lines of text logged by the scriptlet.
+    pub fn scriptlet_lines(&self) -> impl Iterator<Item = &str> {
+        self.scriptlet_output
+            .iter()
+            .filter_map(|entry| match entry.message() {
+                Message::Warning(text) | Message::Error(text) | Message::Other(text) => {
+                    Some(text.as_str())
+                }
+                _ => None,
+            })
+            .flat_map(str::lines)
+    }
+
+    /// Return the package operation, discarding the scriptlet output.
+    #[must_use]
+    pub fn into_package(self) -> T {
+        self.package
+    }
+
+    /// Attach an output entry to the package event.
+    pub(crate) fn push_output(&mut self, entry: Entry) {
+        self.scriptlet_output.push(entry);
+    }
+}
+
+impl<T> Deref for PackageEvent<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.package
+    }
+}
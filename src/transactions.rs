@@ -1,17 +1,99 @@
-use crate::Entry;
-use crate::TransactionsIterator;
+use crate::{Entry, EntryRef, Issuer, IssuerRef, Message, MessageRef};
+use crate::{GroupedIterator, ReverseTransactionsIterator, TransactionsIterator};
 
 /// Group entries into transactions.
-pub trait Transactions: Iterator<Item = Entry> + Sized {
-    /// Return an iterator of [`Transaction`]s.
+pub trait Transactions: Iterator + Sized
+where
+    Self::Item: IntoEntry,
+{
+    /// Return an iterator of [`Transaction`](crate::Transaction)s.
     fn transactions(self) -> TransactionsIterator<Self>;
+
+    /// Return an iterator of [`Transaction`](crate::Transaction)s that yields each transaction
+    /// as soon as its `transaction completed` or `transaction interrupted` entry has been read.
+    ///
+    /// This is useful for streaming sources, such as [`Follow`](crate::Follow).
+    /// Hooks logged after the completion entry are not part of the yielded transactions.
+    fn streaming_transactions(self) -> TransactionsIterator<Self>;
+
+    /// Return an iterator of [`Transaction`](crate::Transaction)s like [`Transactions::streaming_transactions`]
+    /// that continues the transaction left open at a [`Checkpoint`](crate::Checkpoint).
+    ///
+    /// The `replay` entries are obtained with [`Checkpoint::replay`](crate::Checkpoint::replay).
+    /// A transaction that is still open when the entries are exhausted is not yielded,
+    /// so that it can be yielded as a whole after resuming from the next checkpoint.
+    ///
+    /// As with [`Transactions::streaming_transactions`], hooks, warnings and errors logged after
+    /// the completion entry are not part of the yielded transactions, so they may differ from those
+    /// yielded by [`Transactions::transactions`] over the same logfile.
+    fn resumed_transactions<I>(self, replay: I) -> TransactionsIterator<Self>
+    where
+        I: IntoIterator<Item = Entry>;
+
+    /// Return an iterator of [`Transaction`](crate::Transaction)s and entries
+    /// that have been logged outside of any transaction.
+    ///
+    /// Unlike [`Transactions::transactions`], which discards such entries,
+    /// they are yielded as [`Grouped::Orphan`](crate::Grouped::Orphan) after the transaction preceding them.
+    /// Besides package events, these are the entries of invocations of pacman that did not start a transaction
+    /// and lines that are never part of a transaction, such as free text logged by pacman itself.
+    /// Every entry is thus yielded either as part of a transaction or as an orphan.
+    fn grouped(self) -> GroupedIterator<Self>;
+
+    /// Return an iterator of [`Transaction`](crate::Transaction)s from entries ordered
+    /// from the newest to the oldest one, such as those yielded by [`ReverseReader`](crate::ReverseReader).
+    ///
+    /// Transactions are yielded from the newest to the oldest one.
+    fn reverse_transactions(self) -> ReverseTransactionsIterator<Self>;
 }
 
 impl<T> Transactions for T
 where
-    T: Iterator<Item = Entry>,
+    T: Iterator,
+    T::Item: IntoEntry,
 {
     fn transactions(self) -> TransactionsIterator<Self> {
         TransactionsIterator::from(self)
     }
+
+    fn streaming_transactions(self) -> TransactionsIterator<Self> {
+        TransactionsIterator::eager(self)
+    }
+
+    fn resumed_transactions<I>(self, replay: I) -> TransactionsIterator<Self>
+    where
+        I: IntoIterator<Item = Entry>,
+    {
+        TransactionsIterator::resumed(self, replay)
+    }
+
+    fn grouped(self) -> GroupedIterator<Self> {
+        GroupedIterator::from(self)
+    }
+
+    fn reverse_transactions(self) -> ReverseTransactionsIterator<Self> {
+        ReverseTransactionsIterator::from(self)
+    }
+}
+
+/// Entries that can be grouped into transactions.
+pub trait IntoEntry: Into<Entry> {
+    /// Return whether the entry may be part of a transaction.
+    ///
+    /// Entries for which this returns `false` are skipped without being converted into an [`Entry`].
+    fn is_groupable(&self) -> bool;
+}
+
+impl IntoEntry for Entry {
+    fn is_groupable(&self) -> bool {
+        !matches!(self.message(), Message::Other(_))
+            || matches!(self.issuer(), Issuer::Alpm | Issuer::AlpmScriptlet)
+    }
+}
+
+impl IntoEntry for EntryRef<'_> {
+    fn is_groupable(&self) -> bool {
+        !matches!(self.message(), MessageRef::Other(_))
+            || matches!(self.issuer(), IssuerRef::Alpm | IssuerRef::AlpmScriptlet)
+    }
 }
@@ -1,136 +1,146 @@
-use crate::{Entry, Issuer, Package, Transaction};
-use crate::{Message, Upgrade};
-use log::warn;
+use crate::grouper::Grouper;
+use crate::{Entry, IntoEntry, Transaction};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct TransactionsIterator<T>
 where
-    T: Iterator<Item = Entry>,
+    T: Iterator,
+    T::Item: IntoEntry,
 {
     entries: T,
-    start: Option<Entry>,
-    completion: Option<Entry>,
-    installed: Vec<Package>,
-    upgraded: Vec<Upgrade>,
-    reinstalled: Vec<Package>,
-    removed: Vec<Package>,
-    hooks: Vec<Entry>,
+    grouper: Grouper,
+    resumable: bool,
+    done: bool,
 }
 
 impl<T> TransactionsIterator<T>
 where
-    T: Iterator<Item = Entry>,
+    T: Iterator,
+    T::Item: IntoEntry,
 {
-    fn reset(&mut self, start: Entry) {
-        self.start.replace(start);
-        self.completion = None;
-        self.installed.clear();
-        self.upgraded.clear();
-        self.reinstalled.clear();
-        self.removed.clear();
-        self.hooks.clear();
+    /// Create a new iterator that emits transactions as soon as they are completed.
+    pub fn eager(entries: T) -> Self {
+        Self {
+            entries,
+            grouper: Grouper::new(true),
+            resumable: false,
+            done: false,
+        }
     }
 
-    const fn is_within_transaction(&self) -> bool {
-        self.start.is_some() && self.completion.is_none()
-    }
+    /// Create a new iterator that emits transactions as soon as they are completed,
+    /// after restoring its state from the replayed entries of an open transaction.
+    ///
+    /// A transaction that is still open when the entries are exhausted is not emitted.
+    pub fn resumed(entries: T, replay: impl IntoIterator<Item = Entry>) -> Self {
+        let mut grouper = Grouper::new(true);
 
-    fn make_transaction(&mut self) -> Option<Transaction> {
-        self.start.take().map(|start| {
-            Transaction::new(
-                start,
-                self.installed.as_slice().into(),
-                self.upgraded.as_slice().into(),
-                self.reinstalled.as_slice().into(),
-                self.removed.as_slice().into(),
-                self.completion.take(),
-                self.hooks.as_slice().into(),
-            )
-        })
+        for entry in replay.into_iter().filter(IntoEntry::is_groupable) {
+            grouper.push(entry);
+        }
+
+        Self {
+            entries,
+            grouper,
+            resumable: true,
+            done: false,
+        }
     }
 }
 
 impl<T> From<T> for TransactionsIterator<T>
 where
-    T: Iterator<Item = Entry>,
+    T: Iterator,
+    T::Item: IntoEntry,
 {
     fn from(entries: T) -> Self {
         Self {
             entries,
-            start: None,
-            completion: None,
-            installed: Vec::new(),
-            upgraded: Vec::new(),
-            reinstalled: Vec::new(),
-            removed: Vec::new(),
-            hooks: Vec::new(),
+            grouper: Grouper::new(false),
+            resumable: false,
+            done: false,
         }
     }
 }
 
 impl<T> Iterator for TransactionsIterator<T>
 where
-    T: Iterator<Item = Entry>,
+    T: Iterator,
+    T::Item: IntoEntry,
 {
     type Item = Transaction;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if let Some(entry) = self.entries.next() {
-                match entry.message() {
-                    Message::TransactionStarted => {
-                        if let Some(transaction) = self.make_transaction() {
-                            self.reset(entry);
-                            return Some(transaction);
-                        }
-
-                        self.reset(entry);
-                    }
-                    Message::TransactionCompleted => {
-                        self.completion.replace(entry);
-                    }
-                    Message::Other(_) => {
-                        if matches!(entry.issuer(), Issuer::Alpm | Issuer::AlpmScriptlet) {
-                            self.hooks.push(entry);
-                        }
-                    }
-                    Message::Installed(package) => {
-                        if self.is_within_transaction() {
-                            self.installed.push(package.clone());
-                        } else {
-                            warn!("discarding package install outside of transaction: {package:?}");
-                        }
-                    }
-                    Message::Upgraded(upgrade) => {
-                        if self.is_within_transaction() {
-                            self.upgraded.push(upgrade.clone());
-                        } else {
-                            warn!("discarding package upgrade outside of transaction: {upgrade:?}");
-                        }
-                    }
-                    Message::Reinstalled(package) => {
-                        if self.is_within_transaction() {
-                            self.reinstalled.push(package.clone());
-                        } else {
-                            warn!(
-                                "discarding package reinstall outside of transaction: {package:?}"
-                            );
-                        }
-                    }
-                    Message::Removed(package) => {
-                        if self.is_within_transaction() {
-                            self.removed.push(package.clone());
-                        } else {
-                            warn!("discarding package removal outside of transaction: {package:?}");
-                        }
-                    }
-                    Message::StartingFullSystemUpgrade => {
-                        // TODO: Maybe handle this?
-                    }
-                }
-            } else {
-                return self.make_transaction();
+        while !self.done {
+            let Some(entry) = self.entries.next() else {
+                self.done = true;
+                return if self.resumable {
+                    None
+                } else {
+                    self.grouper.finish()
+                };
+            };
+
+            if !entry.is_groupable() {
+                continue;
+            }
+
+            if let Some(transaction) = self.grouper.push(entry.into()) {
+                return Some(transaction);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransactionsIterator;
+    use crate::{Entry, LogReader};
+
+    const LOG: &str = "\
+[2024-03-04T10:00:00+0100] [PACMAN] Running 'pacman -S vim'
+[2024-03-04T10:00:01+0100] [ALPM] transaction started
+[2024-03-04T10:00:02+0100] [ALPM] installed vim (9.1.0142-1)
+[2024-03-04T10:00:03+0100] [ALPM] transaction completed
+";
+
+    /// An iterator that panics when it is advanced after it has been exhausted.
+    struct Unfused<T>(Option<T>);
+
+    impl<T: Iterator> Iterator for Unfused<T> {
+        type Item = T::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let Some(entries) = self.0.as_mut() else {
+                panic!("advanced after exhaustion");
+            };
+
+            let item = entries.next();
+
+            if item.is_none() {
+                self.0 = None;
             }
+
+            item
         }
     }
+
+    fn entries() -> Unfused<impl Iterator<Item = Entry>> {
+        Unfused(Some(LogReader::new(LOG.as_bytes()).map(Result::unwrap)))
+    }
+
+    #[test]
+    fn does_not_advance_exhausted_entries() {
+        let mut transactions = TransactionsIterator::from(entries());
+        assert!(transactions.next().is_some());
+        assert!(transactions.next().is_none());
+        assert!(transactions.next().is_none());
+
+        let mut transactions = TransactionsIterator::eager(entries());
+        assert!(transactions.next().is_some());
+        assert!(transactions.next().is_none());
+        assert!(transactions.next().is_none());
+    }
 }
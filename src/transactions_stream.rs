@@ -0,0 +1,138 @@
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+
+use crate::grouper::Grouper;
+use crate::{IntoEntry, Transaction};
+
+/// Groups a stream of entries into transactions.
+///
+/// This is the [`Stream`] counterpart of the iterator returned by [`Transactions::transactions`](crate::Transactions::transactions).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TransactionsStream<S>
+where
+    S: Stream,
+    S::Item: IntoEntry,
+{
+    entries: S,
+    grouper: Grouper,
+    done: bool,
+}
+
+impl<S> TransactionsStream<S>
+where
+    S: Stream,
+    S::Item: IntoEntry,
+{
+    /// Create a new stream that emits transactions as soon as they are completed.
+    pub fn eager(entries: S) -> Self {
+        Self {
+            entries,
+            grouper: Grouper::new(true),
+            done: false,
+        }
+    }
+}
+
+impl<S> From<S> for TransactionsStream<S>
+where
+    S: Stream,
+    S::Item: IntoEntry,
+{
+    fn from(entries: S) -> Self {
+        Self {
+            entries,
+            grouper: Grouper::new(false),
+            done: false,
+        }
+    }
+}
+
+impl<S> Stream for TransactionsStream<S>
+where
+    S: Stream + Unpin,
+    S::Item: IntoEntry,
+{
+    type Item = Transaction;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        while !this.done {
+            let Some(entry) = ready!(Pin::new(&mut this.entries).poll_next(cx)) else {
+                this.done = true;
+                return Poll::Ready(this.grouper.finish());
+            };
+
+            if !entry.is_groupable() {
+                continue;
+            }
+
+            if let Some(transaction) = this.grouper.push(entry.into()) {
+                return Poll::Ready(Some(transaction));
+            }
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    use futures_core::Stream;
+
+    use super::TransactionsStream;
+    use crate::{Entry, LogReader};
+
+    const LOG: &str = "\
+[2024-03-04T10:00:00+0100] [PACMAN] Running 'pacman -S vim'
+[2024-03-04T10:00:01+0100] [ALPM] transaction started
+[2024-03-04T10:00:02+0100] [ALPM] installed vim (9.1.0142-1)
+[2024-03-04T10:00:03+0100] [ALPM] transaction completed
+";
+
+    /// A stream that panics when it is polled after it has been exhausted.
+    struct Unfused(Option<Vec<Entry>>);
+
+    impl Stream for Unfused {
+        type Item = Entry;
+
+        fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.get_mut();
+            let entries = this.0.as_mut().expect("polled after exhaustion");
+
+            if entries.is_empty() {
+                this.0 = None;
+                Poll::Ready(None)
+            } else {
+                Poll::Ready(Some(entries.remove(0)))
+            }
+        }
+    }
+
+    fn poll(stream: &mut TransactionsStream<Unfused>) -> Poll<Option<crate::Transaction>> {
+        Pin::new(stream).poll_next(&mut Context::from_waker(Waker::noop()))
+    }
+
+    #[test]
+    fn does_not_poll_exhausted_entries() {
+        let entries = || {
+            Unfused(Some(
+                LogReader::new(LOG.as_bytes()).map(Result::unwrap).collect(),
+            ))
+        };
+
+        for mut stream in [
+            TransactionsStream::from(entries()),
+            TransactionsStream::eager(entries()),
+        ] {
+            assert!(matches!(poll(&mut stream), Poll::Ready(Some(_))));
+            assert!(matches!(poll(&mut stream), Poll::Ready(None)));
+            assert!(matches!(poll(&mut stream), Poll::Ready(None)));
+        }
+    }
+}